@@ -0,0 +1,607 @@
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topology {
+    // cells outside of the grid do not exist, and are left out of the
+    // neighbours that are given to the rule
+    Bounded,
+    // the grid wraps around on both axes
+    Toroidal,
+    // the grid is surrounded by an infinite area of background cells, and
+    // grows on every update so that all cells that can differ from the
+    // background are stored
+    Infinite,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighbourhood {
+    offsets: Vec<(i64, i64)>,
+}
+
+impl Neighbourhood {
+    // the offsets are (dx, dy), and are given to the rule in this order
+    pub fn new(offsets: Vec<(i64, i64)>) -> Self {
+        Self { offsets }
+    }
+
+    // the 8 surrounding cells
+    pub fn moore() -> Self {
+        Self::new(
+            Self::kernel_3x3()
+                .offsets
+                .into_iter()
+                .filter(|offset| *offset != (0, 0))
+                .collect(),
+        )
+    }
+
+    // the 4 orthogonally adjacent cells
+    pub fn von_neumann() -> Self {
+        Self::new(vec![(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    // the 3x3 block centered on (and including) the cell, in reading order
+    pub fn kernel_3x3() -> Self {
+        Self::new(
+            (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                .collect(),
+        )
+    }
+
+    pub fn offsets(&self) -> &[(i64, i64)] {
+        &self.offsets
+    }
+
+    fn radius(&self) -> i64 {
+        self.offsets
+            .iter()
+            .map(|(dx, dy)| dx.abs().max(dy.abs()))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+pub fn parse_rows<T>(input: &str, parse_cell: impl Fn(char) -> T) -> Vec<Vec<T>> {
+    input
+        .trim()
+        .lines()
+        .map(|line| line.trim().chars().map(&parse_cell).collect())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    // coordinates of the top-left stored cell (only moves on infinite grids)
+    origin: (i64, i64),
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    topology: Topology,
+    background: Option<T>,
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    fn new(rows: Vec<Vec<T>>, topology: Topology, background: Option<T>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            panic!(
+                "Expected {} cells in every row, found {} in row {}",
+                width,
+                rows[y].len(),
+                y
+            );
+        }
+
+        Self {
+            origin: (0, 0),
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+            topology,
+            background,
+        }
+    }
+
+    pub fn bounded(rows: Vec<Vec<T>>) -> Self {
+        Self::new(rows, Topology::Bounded, None)
+    }
+
+    // coordinates wrap around the grid, so it cannot be empty
+    pub fn toroidal(rows: Vec<Vec<T>>) -> Self {
+        let grid = Self::new(rows, Topology::Toroidal, None);
+
+        if grid.width == 0 || grid.height == 0 {
+            panic!("Expected a non-empty toroidal grid");
+        }
+
+        grid
+    }
+
+    pub fn infinite(rows: Vec<Vec<T>>, background: T) -> Self {
+        Self::new(rows, Topology::Infinite, Some(background))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn origin(&self) -> (i64, i64) {
+        self.origin
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    // only infinite grids have a background
+    pub fn background(&self) -> Option<T> {
+        self.background
+    }
+
+    fn get_stored(&self, x: i64, y: i64) -> Option<T> {
+        let (x, y) = (x - self.origin.0, y - self.origin.1);
+
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            None
+        } else {
+            Some(self.cells[y as usize * self.width + x as usize])
+        }
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<T> {
+        match self.topology {
+            Topology::Bounded => self.get_stored(x, y),
+            Topology::Toroidal => self.get_stored(
+                self.origin.0 + (x - self.origin.0).rem_euclid(self.width as i64),
+                self.origin.1 + (y - self.origin.1).rem_euclid(self.height as i64),
+            ),
+            Topology::Infinite => self.get_stored(x, y).or(self.background),
+        }
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, value: T) {
+        let (stored_x, stored_y) = (x - self.origin.0, y - self.origin.1);
+
+        if stored_x < 0
+            || stored_y < 0
+            || stored_x >= self.width as i64
+            || stored_y >= self.height as i64
+        {
+            panic!("Cannot set ({}, {}) outside of the stored grid", x, y);
+        }
+
+        self.cells[stored_y as usize * self.width + stored_x as usize] = value;
+    }
+
+    // every stored cell together with its coordinates, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), T)> + '_ {
+        self.cells.iter().enumerate().map(|(i, cell)| {
+            (
+                (
+                    self.origin.0 + (i % self.width) as i64,
+                    self.origin.1 + (i / self.width) as i64,
+                ),
+                *cell,
+            )
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // only counts the stored cells
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| predicate(cell)).count()
+    }
+
+    pub fn neighbour_coords(
+        &self,
+        x: i64,
+        y: i64,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<(i64, i64)> {
+        neighbourhood
+            .offsets()
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|(x, y)| self.get(*x, *y).is_some())
+            .collect()
+    }
+
+    fn collect_neighbours(
+        &self,
+        x: i64,
+        y: i64,
+        neighbourhood: &Neighbourhood,
+        buffer: &mut Vec<T>,
+    ) {
+        buffer.clear();
+        buffer.extend(
+            neighbourhood
+                .offsets()
+                .iter()
+                .filter_map(|(dx, dy)| self.get(x + dx, y + dy)),
+        );
+    }
+
+    // synchronous update, every cell is computed from this generation
+    fn apply(&self, neighbourhood: &Neighbourhood, rule: &dyn Fn(T, &[T]) -> T) -> Self {
+        let mut buffer = Vec::with_capacity(neighbourhood.offsets().len());
+
+        let (origin, width, height, background) = match self.background {
+            Some(background) => {
+                let radius = neighbourhood.radius();
+                let all_background = vec![background; neighbourhood.offsets().len()];
+
+                (
+                    (self.origin.0 - radius, self.origin.1 - radius),
+                    self.width + 2 * radius as usize,
+                    self.height + 2 * radius as usize,
+                    Some(rule(background, &all_background)),
+                )
+            }
+            None => (self.origin, self.width, self.height, self.background),
+        };
+
+        let cells = (0..height as i64)
+            .flat_map(|y| (0..width as i64).map(move |x| (origin.0 + x, origin.1 + y)))
+            .map(|(x, y)| {
+                self.collect_neighbours(x, y, neighbourhood, &mut buffer);
+                rule(self.get(x, y).unwrap(), &buffer)
+            })
+            .collect();
+
+        Self {
+            origin,
+            width,
+            height,
+            cells,
+            topology: self.topology,
+            background,
+        }
+    }
+
+    // infinite grids are compared over the area they cover together, since
+    // the same image can be stored with different amounts of background
+    fn same_state(&self, other: &Self) -> bool {
+        if self.topology != Topology::Infinite {
+            return self.cells == other.cells;
+        }
+
+        let min = (
+            self.origin.0.min(other.origin.0),
+            self.origin.1.min(other.origin.1),
+        );
+        let max = (
+            (self.origin.0 + self.width as i64).max(other.origin.0 + other.width as i64),
+            (self.origin.1 + self.height as i64).max(other.origin.1 + other.height as i64),
+        );
+
+        self.background == other.background
+            && (min.1..max.1).all(|y| (min.0..max.0).all(|x| self.get(x, y) == other.get(x, y)))
+    }
+
+    // what same_state() compares, in a form that can be hashed: infinite
+    // grids are cut down to the smallest area that holds every cell that
+    // differs from the background
+    fn state_key(&self) -> StateKey<T> {
+        let background = match (self.topology, self.background) {
+            (Topology::Infinite, Some(background)) => background,
+            _ => return (None, (0, 0), self.width, self.cells.clone()),
+        };
+
+        let differing = self
+            .iter()
+            .filter(|(_, cell)| *cell != background)
+            .map(|(coord, _)| coord)
+            .collect::<Vec<_>>();
+        if differing.is_empty() {
+            return (self.background, (0, 0), 0, vec![]);
+        }
+
+        let min = (
+            differing.iter().map(|coord| coord.0).min().unwrap(),
+            differing.iter().map(|coord| coord.1).min().unwrap(),
+        );
+        let max = (
+            differing.iter().map(|coord| coord.0).max().unwrap(),
+            differing.iter().map(|coord| coord.1).max().unwrap(),
+        );
+        let cells = (min.1..=max.1)
+            .flat_map(|y| (min.0..=max.0).map(move |x| (x, y)))
+            .map(|(x, y)| self.get(x, y).unwrap())
+            .collect();
+
+        (self.background, min, (max.0 - min.0 + 1) as usize, cells)
+    }
+}
+
+// background, top-left corner, width and cells
+type StateKey<T> = (Option<T>, (i64, i64), usize, Vec<T>);
+
+type Rule<'a, T> = Box<dyn Fn(T, &[T]) -> T + 'a>;
+
+struct Phase<'a, T> {
+    neighbourhood: Neighbourhood,
+    rule: Rule<'a, T>,
+    until_stable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    // the first generation that is part of the cycle
+    pub start: usize,
+    pub period: usize,
+}
+
+// a generation consists of every phase applied once, in order. The rule
+// receives the cell itself, and its neighbours in the neighbourhood's order
+pub struct Automaton<'a, T> {
+    grid: Grid<T>,
+    phases: Vec<Phase<'a, T>>,
+    generation: usize,
+}
+
+impl<'a, T: Copy + PartialEq> Automaton<'a, T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        Self {
+            grid,
+            phases: vec![],
+            generation: 0,
+        }
+    }
+
+    pub fn with_phase(
+        mut self,
+        neighbourhood: Neighbourhood,
+        rule: impl Fn(T, &[T]) -> T + 'a,
+    ) -> Self {
+        self.phases.push(Phase {
+            neighbourhood,
+            rule: Box::new(rule),
+            until_stable: false,
+        });
+        self
+    }
+
+    // the rule is applied over and over until the grid stops changing,
+    // which is useful for cascades within a single generation
+    pub fn with_phase_until_stable(
+        mut self,
+        neighbourhood: Neighbourhood,
+        rule: impl Fn(T, &[T]) -> T + 'a,
+    ) -> Self {
+        self.phases.push(Phase {
+            neighbourhood,
+            rule: Box::new(rule),
+            until_stable: true,
+        });
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // returns whether any cell changed
+    pub fn step(&mut self) -> bool {
+        let mut changed = false;
+
+        self.phases.iter().for_each(|phase| loop {
+            let next = self.grid.apply(&phase.neighbourhood, phase.rule.as_ref());
+            let phase_changed = !next.same_state(&self.grid);
+
+            self.grid = next;
+            changed |= phase_changed;
+
+            if !phase.until_stable || !phase_changed {
+                break;
+            }
+        });
+
+        self.generation += 1;
+        changed
+    }
+
+    // the generation on which nothing changed anymore
+    pub fn run_until_fixed_point(&mut self, max_generations: usize) -> Option<usize> {
+        while self.generation < max_generations {
+            if !self.step() {
+                return Some(self.generation);
+            }
+        }
+
+        None
+    }
+
+    // grids are compared the same way as in step(), so an infinite grid
+    // cycles even though it is stored bigger on every generation
+    pub fn find_cycle(&mut self, max_generations: usize) -> Option<Cycle>
+    where
+        T: Hash + Eq,
+    {
+        let mut seen = HashMap::new();
+        seen.insert(self.grid.state_key(), self.generation);
+
+        while self.generation < max_generations {
+            self.step();
+
+            if let Some(start) = seen.insert(self.grid.state_key(), self.generation) {
+                return Some(Cycle {
+                    start,
+                    period: self.generation - start,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn parse_life(input: &str) -> Vec<Vec<bool>> {
+        parse_rows(input, |c| c == '#')
+    }
+
+    fn life_rule(cell: bool, neighbours: &[bool]) -> bool {
+        matches!(
+            (cell, neighbours.iter().filter(|n| **n).count()),
+            (true, 2) | (_, 3)
+        )
+    }
+
+    #[test]
+    fn test_neighbourhoods() {
+        assert_eq!(Neighbourhood::moore().offsets().len(), 8);
+        assert_eq!(Neighbourhood::kernel_3x3().offsets()[0], (-1, -1));
+        assert_eq!(Neighbourhood::kernel_3x3().offsets()[4], (0, 0));
+        assert_eq!(Neighbourhood::von_neumann().radius(), 1);
+        assert_eq!(Neighbourhood::new(vec![(2, -3)]).radius(), 3);
+    }
+
+    #[test]
+    fn test_get_topologies() {
+        let rows = parse_life("#..\n...\n..#");
+
+        let bounded = Grid::bounded(rows.clone());
+        assert_eq!(bounded.get(0, 0), Some(true));
+        assert_eq!(bounded.get(-1, 0), None);
+
+        let toroidal = Grid::toroidal(rows.clone());
+        assert_eq!(toroidal.get(-1, -1), Some(true));
+        assert_eq!(toroidal.get(3, 3), Some(true));
+        assert_eq!(toroidal.get(4, 3), Some(false));
+
+        let infinite = Grid::infinite(rows, false);
+        assert_eq!(infinite.get(-100, 5), Some(false));
+        assert_eq!(infinite.get(2, 2), Some(true));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_toroidal() {
+        Grid::<bool>::toroidal(vec![vec![]]);
+    }
+
+    #[test]
+    fn test_neighbour_coords() {
+        let grid = Grid::bounded(parse_life("...\n...\n..."));
+
+        assert_eq!(
+            grid.neighbour_coords(0, 0, &Neighbourhood::moore())
+                .into_iter()
+                .collect::<HashSet<_>>(),
+            HashSet::from([(1, 0), (0, 1), (1, 1)])
+        );
+        assert_eq!(
+            grid.neighbour_coords(1, 1, &Neighbourhood::moore()).len(),
+            8
+        );
+
+        let grid = Grid::toroidal(parse_life("...\n...\n..."));
+        assert_eq!(
+            grid.neighbour_coords(0, 0, &Neighbourhood::von_neumann()),
+            vec![(0, -1), (-1, 0), (1, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_blinker_cycle() {
+        let mut automaton = Automaton::new(Grid::bounded(parse_life(
+            ".....\n..#..\n..#..\n..#..\n.....",
+        )))
+        .with_phase(Neighbourhood::moore(), life_rule);
+
+        assert_eq!(
+            automaton.find_cycle(10),
+            Some(Cycle {
+                start: 0,
+                period: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_infinite_blinker_cycle() {
+        // the grid grows on every generation, but the blinker doesn't
+        let mut automaton = Automaton::new(Grid::infinite(parse_life("#\n#\n#"), false))
+            .with_phase(Neighbourhood::moore(), life_rule);
+
+        assert_eq!(
+            automaton.find_cycle(10),
+            Some(Cycle {
+                start: 0,
+                period: 2
+            })
+        );
+        assert_eq!(automaton.grid().origin(), (-2, -2));
+    }
+
+    #[test]
+    fn test_block_fixed_point() {
+        let mut automaton = Automaton::new(Grid::toroidal(parse_life("....\n.##.\n.#..\n....")))
+            .with_phase(Neighbourhood::moore(), life_rule);
+
+        // becomes a block after the first generation, which never changes
+        assert_eq!(automaton.run_until_fixed_point(10), Some(2));
+        assert_eq!(automaton.grid().count(|cell| *cell), 4);
+    }
+
+    #[test]
+    fn test_infinite_growth() {
+        // a cell is lit if anything in its kernel is lit
+        let mut automaton = Automaton::new(Grid::infinite(parse_life("#"), false))
+            .with_phase(Neighbourhood::kernel_3x3(), |_, kernel| {
+                kernel.iter().any(|lit| *lit)
+            });
+
+        automaton.step();
+        automaton.step();
+
+        assert_eq!(automaton.grid().origin(), (-2, -2));
+        assert_eq!(automaton.grid().count(|cell| *cell), 25);
+        assert_eq!(automaton.grid().background(), Some(false));
+    }
+
+    #[test]
+    fn test_infinite_background_flip() {
+        // every cell inverts itself, including the infinite background
+        let mut automaton = Automaton::new(Grid::infinite(parse_life("#"), false))
+            .with_phase(Neighbourhood::new(vec![]), |cell, _| !cell);
+
+        automaton.step();
+        assert_eq!(automaton.grid().background(), Some(true));
+        assert_eq!(automaton.grid().get(0, 0), Some(false));
+        assert_eq!(automaton.grid().get(5, 5), Some(true));
+    }
+
+    #[test]
+    fn test_phase_until_stable() {
+        // a lit cell spreads to the right, until it hits the edge
+        let mut automaton = Automaton::new(Grid::bounded(parse_life("#....\n.....")))
+            .with_phase_until_stable(Neighbourhood::new(vec![(-1, 0)]), |cell, left| {
+                cell || left.first().copied().unwrap_or(false)
+            });
+
+        assert!(automaton.step());
+        assert_eq!(automaton.grid().count(|cell| *cell), 5);
+        assert!(!automaton.step());
+        assert_eq!(automaton.generation(), 2);
+    }
+}
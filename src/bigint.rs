@@ -0,0 +1,213 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul},
+};
+
+// an unsigned integer of any size, in base 2^32 with the least significant
+// limb first and no trailing zero limbs (so zero has no limbs)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // most significant bit first
+    pub fn from_bits(bits: impl IntoIterator<Item = bool>) -> Self {
+        let bits = bits.into_iter().collect::<Vec<_>>();
+        let mut limbs = vec![0; bits.len().div_ceil(32)];
+
+        bits.iter()
+            .rev()
+            .enumerate()
+            .filter(|(_, bit)| **bit)
+            .for_each(|(i, _)| limbs[i / 32] |= 1 << (i % 32));
+
+        Self { limbs }.normalize()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u64),
+            2 => Some(self.limbs[0] as u64 | (self.limbs[1] as u64) << 32),
+            _ => None,
+        }
+    }
+
+    fn normalize(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    // (quotient, remainder)
+    fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        let mut remainder = 0u64;
+        let mut limbs = vec![0; self.limbs.len()];
+
+        (0..self.limbs.len()).rev().for_each(|i| {
+            let current = (remainder << 32) | self.limbs[i] as u64;
+            limbs[i] = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        });
+
+        (Self { limbs }.normalize(), remainder as u32)
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        }
+        .normalize()
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut carry = 0u64;
+        let mut limbs = (0..self.limbs.len().max(other.limbs.len()))
+            .map(|i| {
+                let sum = carry
+                    + self.limbs.get(i).copied().unwrap_or(0) as u64
+                    + other.limbs.get(i).copied().unwrap_or(0) as u64;
+                carry = sum >> 32;
+                sum as u32
+            })
+            .collect::<Vec<_>>();
+        limbs.push(carry as u32);
+
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    // schoolbook multiplication, which needs additions and shifts
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        self.limbs.iter().enumerate().for_each(|(i, a)| {
+            let mut carry = 0u64;
+            other.limbs.iter().enumerate().for_each(|(j, b)| {
+                let product = limbs[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            });
+            limbs[i + other.limbs.len()] = carry as u32;
+        });
+
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // groups of 9 digits, least significant first
+        let mut groups = vec![];
+        let mut current = self.clone();
+        while !current.is_zero() {
+            let (quotient, remainder) = current.div_rem_small(1_000_000_000);
+            groups.push(remainder);
+            current = quotient;
+        }
+
+        write!(f, "{}", groups.last().unwrap())?;
+        groups
+            .iter()
+            .rev()
+            .skip(1)
+            .try_for_each(|group| write!(f, "{:09}", group))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        let a = BigUint::from(u64::MAX);
+        let sum = &a + &BigUint::from(1);
+
+        assert_eq!(sum.to_u64(), None);
+        assert_eq!(sum.to_string(), "18446744073709551616");
+        assert_eq!(&BigUint::zero() + &BigUint::zero(), BigUint::zero());
+    }
+
+    #[test]
+    fn test_from_bits() {
+        assert_eq!(BigUint::from_bits([]), BigUint::zero());
+        assert_eq!(
+            BigUint::from_bits([false, true, true, false]).to_u64(),
+            Some(6)
+        );
+        assert_eq!(
+            BigUint::from_bits(std::iter::once(true).chain([false; 64])).to_string(),
+            "18446744073709551616"
+        );
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = BigUint::from(u64::MAX);
+
+        assert_eq!(
+            (&a * &a).to_string(),
+            "340282366920938463426481119284349108225"
+        );
+        assert_eq!(&a * &BigUint::zero(), BigUint::zero());
+        assert_eq!((&BigUint::from(6) * &BigUint::from(7)).to_u64(), Some(42));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(
+            BigUint::from(1_000_000_000_000_000_007).to_string(),
+            "1000000000000000007"
+        );
+    }
+
+    #[test]
+    fn test_cmp() {
+        assert!(BigUint::from(1 << 40) > BigUint::from(1 << 33));
+        assert!(BigUint::from(3) < BigUint::from(4));
+        assert!(&BigUint::from(u64::MAX) + &BigUint::from(1) > BigUint::from(u64::MAX));
+    }
+}
@@ -1,3 +1,5 @@
+use advent_of_code_2021_rust::{export::Table, svg};
+
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
 fn parse_numbers(input: &str) -> Vec<i32> {
@@ -9,27 +11,133 @@ fn parse_numbers(input: &str) -> Vec<i32> {
         .collect()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparator {
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparator {
+    fn holds(&self, previous: i64, next: i64) -> bool {
+        match self {
+            Comparator::Greater => next > previous,
+            Comparator::GreaterOrEqual => next >= previous,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    // sums every window, and compares each sum with the previous one
+    WindowSum,
+    // consecutive windows share everything but their endpoints, so comparing
+    // the sums is the same as comparing the first element of the previous
+    // window with the last element of the next one
+    Endpoints,
+}
+
+// how many windows compare favourably with the window before them
+fn count_increases(
+    series: &[i32],
+    window: usize,
+    comparison: Comparison,
+    comparator: Comparator,
+) -> usize {
+    if window == 0 {
+        panic!("Expected a window of at least 1");
+    }
+
+    match comparison {
+        Comparison::WindowSum => {
+            let sums = series
+                .windows(window)
+                .map(|values| values.iter().map(|value| *value as i64).sum::<i64>())
+                .collect::<Vec<_>>();
+            sums.windows(2)
+                .filter(|pair| comparator.holds(pair[0], pair[1]))
+                .count()
+        }
+        Comparison::Endpoints => series
+            .iter()
+            .zip(series.iter().skip(window))
+            .filter(|(x, y)| comparator.holds(**x as i64, **y as i64))
+            .count(),
+    }
+}
+
 fn p1(input: &str) -> String {
-    let numbers = parse_numbers(input);
-    numbers
-        .iter()
-        .zip(numbers.iter().skip(1))
-        .filter(|(x, y)| y > x)
-        .count()
-        .to_string()
+    count_increases(
+        &parse_numbers(input),
+        1,
+        Comparison::Endpoints,
+        Comparator::Greater,
+    )
+    .to_string()
 }
 
 fn p2(input: &str) -> String {
-    let numbers = parse_numbers(input);
-    numbers
-        .iter()
-        .zip(numbers.iter().skip(3))
-        .filter(|(x, y)| y > x)
-        .count()
-        .to_string()
+    count_increases(
+        &parse_numbers(input),
+        3,
+        Comparison::Endpoints,
+        Comparator::Greater,
+    )
+    .to_string()
+}
+
+// depth over index
+fn get_depth_table(input: &str) -> Table {
+    let mut table = Table::new(&["index", "depth"]);
+    parse_numbers(input)
+        .into_iter()
+        .enumerate()
+        .for_each(|(i, depth)| table.push(vec![i as i64, depth as i64]));
+    table
+}
+
+fn export(input: &str, format: &str) -> String {
+    let table = get_depth_table(input);
+
+    match format {
+        "csv" => table.to_csv(),
+        "json" => table.to_json(),
+        "svg" => svg::line_plot(&[("depth", table.column("depth"))], 800, 400),
+        _ => panic!("Unknown export format {}", format),
+    }
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d01 -- export <csv|json|svg>
+    if args.get(1).map(String::as_str) == Some("export") {
+        print!(
+            "{}",
+            export(ACTUAL_INPUT, args.get(2).expect("Missing format"))
+        );
+        return;
+    }
+
+    // cargo run --bin d01 -- count <window> [greater|greater-or-equal] [endpoints|sum]
+    if args.get(1).map(String::as_str) == Some("count") {
+        let window = args.get(2).expect("Missing window").parse().unwrap();
+        let comparator = match args.get(3).map(String::as_str) {
+            Some("greater") | None => Comparator::Greater,
+            Some("greater-or-equal") => Comparator::GreaterOrEqual,
+            Some(comparator) => panic!("Unknown comparator {}", comparator),
+        };
+        let comparison = match args.get(4).map(String::as_str) {
+            Some("endpoints") | None => Comparison::Endpoints,
+            Some("sum") => Comparison::WindowSum,
+            Some(comparison) => panic!("Unknown comparison {}", comparison),
+        };
+        println!(
+            "{}",
+            count_increases(&parse_numbers(ACTUAL_INPUT), window, comparison, comparator)
+        );
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -37,6 +145,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_2021_rust::rng::Rng;
 
     const SAMPLE_INPUT: &str = r"
 199
@@ -51,6 +160,80 @@ mod tests {
 263
 ";
 
+    fn generate_series(rng: &mut Rng) -> Vec<i32> {
+        let len = rng.below(40);
+        // a small range, so equal neighbours are common
+        (0..len).map(|_| rng.below(7) as i32 - 3).collect()
+    }
+
+    #[test]
+    fn test_count_increases() {
+        let series = [1, 1, 2, 1, 3];
+
+        assert_eq!(
+            count_increases(&series, 1, Comparison::WindowSum, Comparator::Greater),
+            2
+        );
+        assert_eq!(
+            count_increases(
+                &series,
+                1,
+                Comparison::WindowSum,
+                Comparator::GreaterOrEqual
+            ),
+            3
+        );
+        // sums 2, 3, 3, 4
+        assert_eq!(
+            count_increases(&series, 2, Comparison::WindowSum, Comparator::Greater),
+            2
+        );
+        assert_eq!(
+            count_increases(&series, 5, Comparison::Endpoints, Comparator::Greater),
+            0
+        );
+        assert_eq!(
+            count_increases(&[], 3, Comparison::Endpoints, Comparator::Greater),
+            0
+        );
+    }
+
+    #[test]
+    fn test_endpoints_equals_window_sum() {
+        let mut rng = Rng::new(0x2021_0001);
+
+        (0..500).for_each(|_| {
+            let series = generate_series(&mut rng);
+            let window = rng.below(6) as usize + 1;
+
+            [Comparator::Greater, Comparator::GreaterOrEqual]
+                .into_iter()
+                .for_each(|comparator| {
+                    assert_eq!(
+                        count_increases(&series, window, Comparison::Endpoints, comparator),
+                        count_increases(&series, window, Comparison::WindowSum, comparator),
+                        "{:?} {} {:?}",
+                        series,
+                        window,
+                        comparator
+                    );
+                });
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_count_increases_empty_window() {
+        count_increases(&[1, 2], 0, Comparison::Endpoints, Comparator::Greater);
+    }
+
+    #[test]
+    fn test_export() {
+        assert!(export(SAMPLE_INPUT, "csv").starts_with("index,depth\n0,199\n1,200\n"));
+        assert!(export(SAMPLE_INPUT, "json").ends_with("{\"index\":9,\"depth\":263}]\n"));
+        assert!(export(SAMPLE_INPUT, "svg").contains("<polyline"));
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(p1(SAMPLE_INPUT), "7");
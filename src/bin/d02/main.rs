@@ -1,14 +1,18 @@
+use advent_of_code_2021_rust::{export::Table, svg};
+
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Instruction {
     Forward(i32),
+    Back(i32),
     Down(i32),
     Up(i32),
 }
 
 impl Instruction {
     fn parse(line: &str) -> Self {
-        let parts = line.split(' ').collect::<Vec<_>>();
+        let parts = line.split_whitespace().collect::<Vec<_>>();
 
         if parts.len() != 2 {
             panic!("Expected 2 parts, found '{}'", line);
@@ -20,14 +24,25 @@ impl Instruction {
 
         match parts[0] {
             "forward" => Instruction::Forward(count),
+            "back" => Instruction::Back(count),
             "down" => Instruction::Down(count),
             "up" => Instruction::Up(count),
             _ => panic!("Expected action for first part, found '{}'", line),
         }
     }
+
+    // anything after a '#' is a comment, and blank lines are skipped
+    fn parse_program(input: &str) -> Vec<Self> {
+        input
+            .lines()
+            .map(|line| line.split('#').next().unwrap().trim())
+            .filter(|line| !line.is_empty())
+            .map(Instruction::parse)
+            .collect()
+    }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 struct Submarine {
     horizontal: i32,
     depth: i32,
@@ -35,74 +50,154 @@ struct Submarine {
 }
 
 impl Submarine {
-    fn p1_do(&self, instruction: &Instruction) -> Self {
+    fn get_answer(&self) -> String {
+        (self.horizontal * self.depth).to_string()
+    }
+}
+
+// the movement model, i.e. how an instruction changes the submarine
+trait Navigation {
+    fn apply(&self, submarine: &Submarine, instruction: &Instruction) -> Submarine;
+}
+
+// p1: up and down change the depth directly
+struct DirectNavigation;
+
+impl Navigation for DirectNavigation {
+    fn apply(&self, submarine: &Submarine, instruction: &Instruction) -> Submarine {
         match *instruction {
             Instruction::Forward(x) => Submarine {
-                horizontal: self.horizontal + x,
-                depth: self.depth,
-                aim: self.aim,
+                horizontal: submarine.horizontal + x,
+                ..*submarine
+            },
+            Instruction::Back(x) => Submarine {
+                horizontal: submarine.horizontal - x,
+                ..*submarine
             },
             Instruction::Down(x) => Submarine {
-                horizontal: self.horizontal,
-                depth: self.depth + x,
-                aim: self.aim,
+                depth: submarine.depth + x,
+                ..*submarine
             },
             Instruction::Up(x) => Submarine {
-                horizontal: self.horizontal,
-                depth: self.depth - x,
-                aim: self.aim,
+                depth: submarine.depth - x,
+                ..*submarine
             },
         }
     }
+}
+
+// p2: up and down change the aim, and moving follows the aim
+struct AimedNavigation;
 
-    fn p2_do(&self, instruction: &Instruction) -> Self {
+impl Navigation for AimedNavigation {
+    fn apply(&self, submarine: &Submarine, instruction: &Instruction) -> Submarine {
         match *instruction {
             Instruction::Forward(x) => Submarine {
-                horizontal: self.horizontal + x,
-                depth: self.depth + self.aim * x,
-                aim: self.aim,
+                horizontal: submarine.horizontal + x,
+                depth: submarine.depth + submarine.aim * x,
+                ..*submarine
+            },
+            Instruction::Back(x) => Submarine {
+                horizontal: submarine.horizontal - x,
+                depth: submarine.depth - submarine.aim * x,
+                ..*submarine
             },
             Instruction::Down(x) => Submarine {
-                horizontal: self.horizontal,
-                depth: self.depth,
-                aim: self.aim + x,
+                aim: submarine.aim + x,
+                ..*submarine
             },
             Instruction::Up(x) => Submarine {
-                horizontal: self.horizontal,
-                depth: self.depth,
-                aim: self.aim - x,
+                aim: submarine.aim - x,
+                ..*submarine
             },
         }
     }
+}
 
-    fn get_answer(&self) -> String {
-        (self.horizontal * self.depth).to_string()
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Step {
+    instruction: Instruction,
+    // the state after the instruction
+    submarine: Submarine,
 }
 
-fn p1(input: &str) -> String {
-    input
-        .trim()
-        .lines()
-        .map(Instruction::parse)
-        .fold(Submarine::default(), |acc, instruction| {
-            acc.p1_do(&instruction)
+// the full trajectory, one step per instruction
+fn run(instructions: &[Instruction], navigation: &dyn Navigation) -> Vec<Step> {
+    instructions
+        .iter()
+        .scan(Submarine::default(), |submarine, instruction| {
+            *submarine = navigation.apply(submarine, instruction);
+            Some(Step {
+                instruction: *instruction,
+                submarine: *submarine,
+            })
         })
+        .collect()
+}
+
+fn solve(input: &str, navigation: &dyn Navigation) -> String {
+    run(&Instruction::parse_program(input), navigation)
+        .last()
+        .map(|step| step.submarine)
+        .unwrap_or_default()
         .get_answer()
 }
 
+fn p1(input: &str) -> String {
+    solve(input, &DirectNavigation)
+}
+
 fn p2(input: &str) -> String {
-    input
-        .trim()
-        .lines()
-        .map(Instruction::parse)
-        .fold(Submarine::default(), |acc, instruction| {
-            acc.p2_do(&instruction)
-        })
-        .get_answer()
+    solve(input, &AimedNavigation)
+}
+
+// (horizontal, depth, aim) after every step, starting from the surface
+fn get_trajectory_table(trajectory: &[Step]) -> Table {
+    let mut table = Table::new(&["step", "horizontal", "depth", "aim"]);
+    std::iter::once(Submarine::default())
+        .chain(trajectory.iter().map(|step| step.submarine))
+        .enumerate()
+        .for_each(|(i, submarine)| {
+            table.push(vec![
+                i as i64,
+                submarine.horizontal as i64,
+                submarine.depth as i64,
+                submarine.aim as i64,
+            ])
+        });
+    table
+}
+
+fn export(input: &str, navigation: &dyn Navigation, format: &str) -> String {
+    let table = get_trajectory_table(&run(&Instruction::parse_program(input), navigation));
+
+    match format {
+        "csv" => table.to_csv(),
+        "json" => table.to_json(),
+        "svg" => svg::line_plot(
+            &["horizontal", "depth", "aim"].map(|column| (column, table.column(column))),
+            800,
+            400,
+        ),
+        _ => panic!("Unknown export format {}", format),
+    }
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d02 -- export <csv|json|svg> [p1|p2]
+    if args.get(1).map(String::as_str) == Some("export") {
+        let format = args.get(2).expect("Missing format");
+        let navigation: &dyn Navigation = match args.get(3).map(String::as_str) {
+            Some("p1") => &DirectNavigation,
+            Some("p2") | None => &AimedNavigation,
+            Some(part) => panic!("Unknown part {}", part),
+        };
+        print!("{}", export(ACTUAL_INPUT, navigation, format));
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -120,6 +215,87 @@ down 8
 forward 2
 ";
 
+    #[test]
+    fn test_parse_program() {
+        assert_eq!(
+            Instruction::parse_program(
+                r"
+# a comment on its own line
+forward 5   # trailing comment
+
+back 2
+up   3
+"
+            ),
+            vec![
+                Instruction::Forward(5),
+                Instruction::Back(2),
+                Instruction::Up(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_trajectory() {
+        let trajectory = run(&Instruction::parse_program(SAMPLE_INPUT), &AimedNavigation);
+
+        assert_eq!(trajectory.len(), 6);
+        assert_eq!(
+            trajectory[1],
+            Step {
+                instruction: Instruction::Down(5),
+                submarine: Submarine {
+                    horizontal: 5,
+                    depth: 0,
+                    aim: 5
+                }
+            }
+        );
+        assert_eq!(
+            trajectory[2].submarine,
+            Submarine {
+                horizontal: 13,
+                depth: 40,
+                aim: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_back() {
+        let program = Instruction::parse_program("down 2\nforward 5\nback 3");
+
+        assert_eq!(
+            run(&program, &DirectNavigation).last().unwrap().submarine,
+            Submarine {
+                horizontal: 2,
+                depth: 2,
+                aim: 0
+            }
+        );
+        assert_eq!(
+            run(&program, &AimedNavigation).last().unwrap().submarine,
+            Submarine {
+                horizontal: 2,
+                depth: 4,
+                aim: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_export() {
+        let csv = export(SAMPLE_INPUT, &AimedNavigation, "csv");
+        assert!(csv.starts_with("step,horizontal,depth,aim\n0,0,0,0\n1,5,0,0\n2,5,0,5\n"));
+        assert!(csv.ends_with("6,15,60,10\n"));
+
+        let json = export(SAMPLE_INPUT, &DirectNavigation, "json");
+        assert!(json.ends_with("{\"step\":6,\"horizontal\":15,\"depth\":10,\"aim\":0}]\n"));
+
+        let svg = export(SAMPLE_INPUT, &AimedNavigation, "svg");
+        assert_eq!(svg.matches("<polyline").count(), 3);
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(p1(SAMPLE_INPUT), "150");
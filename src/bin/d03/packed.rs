@@ -0,0 +1,284 @@
+use advent_of_code_2021_rust::bigint::BigUint;
+
+const ACTUAL_INPUT: &str = include_str!("input.txt");
+
+// an entry of any width, with column 0 (the most significant bit) stored in
+// the top bit of the first word, so comparing the words compares the values
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Bits {
+    words: Vec<u64>,
+}
+
+impl Bits {
+    fn new(width: usize) -> Self {
+        Self {
+            words: vec![0; width.div_ceil(64)],
+        }
+    }
+
+    fn parse(line: &str) -> Self {
+        let mut bits = Self::new(line.len());
+        line.chars().enumerate().for_each(|(i, c)| match c {
+            '0' => {}
+            '1' => bits.set(i),
+            _ => panic!("Expected a binary digit, found '{}'", line),
+        });
+        bits
+    }
+
+    fn get(&self, column: usize) -> bool {
+        (self.words[column / 64] >> (63 - column % 64)) & 1 == 1
+    }
+
+    fn set(&mut self, column: usize) {
+        self.words[column / 64] |= 1 << (63 - column % 64);
+    }
+
+    fn to_biguint(&self, width: usize) -> BigUint {
+        BigUint::from_bits((0..width).map(|column| self.get(column)))
+    }
+}
+
+#[derive(Debug)]
+struct Report {
+    width: usize,
+    // sorted, so the entries sharing a prefix are always next to each other
+    entries: Vec<Bits>,
+    // bit i of column c is set when entry i has a 1 in column c
+    columns: Vec<Vec<u64>>,
+}
+
+impl Report {
+    fn parse_input(input: &str) -> Self {
+        let lines = input.trim().lines().collect::<Vec<_>>();
+        let width = lines[0].len();
+
+        let mut entries = lines
+            .into_iter()
+            .map(|line| {
+                if line.len() != width {
+                    panic!("Expected {} bits, found '{}'", width, line);
+                }
+                Bits::parse(line)
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        let mut columns = vec![vec![0; entries.len().div_ceil(64)]; width];
+        entries.iter().enumerate().for_each(|(i, entry)| {
+            columns.iter_mut().enumerate().for_each(|(column, bits)| {
+                if entry.get(column) {
+                    bits[i / 64] |= 1 << (i % 64);
+                }
+            })
+        });
+
+        Self {
+            width,
+            entries,
+            columns,
+        }
+    }
+
+    fn count_ones(&self, column: usize) -> usize {
+        self.columns[column]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // (gamma rate, epsilon rate)
+    fn get_rates(&self) -> (Bits, Bits) {
+        let mut gamma_rate = Bits::new(self.width);
+        let mut epsilon_rate = Bits::new(self.width);
+
+        (0..self.width).for_each(|column| {
+            if self.count_ones(column) * 2 > self.entries.len() {
+                gamma_rate.set(column);
+            } else {
+                epsilon_rate.set(column);
+            }
+        });
+
+        (gamma_rate, epsilon_rate)
+    }
+
+    // narrows down the range of entries sharing a prefix, one column at a
+    // time, without copying any of them
+    fn find_entry_by_bit_criteria(&self, criteria_type: BitCriteriaType) -> &Bits {
+        let mut range = 0..self.entries.len();
+
+        for column in 0..self.width {
+            if range.len() == 1 {
+                break;
+            }
+
+            let split = range.start
+                + self.entries[range.clone()].partition_point(|entry| !entry.get(column));
+            let zeroes = range.start..split;
+            let ones = split..range.end;
+
+            let selected = match criteria_type {
+                // the most common bit, or 1 on a tie
+                BitCriteriaType::Oxygen => {
+                    if ones.len() >= zeroes.len() {
+                        ones
+                    } else {
+                        zeroes
+                    }
+                }
+                // the least common bit, or 0 on a tie
+                BitCriteriaType::CO2 => {
+                    if zeroes.len() <= ones.len() {
+                        zeroes
+                    } else {
+                        ones
+                    }
+                }
+            };
+
+            // every entry has the same bit, so there's nothing to filter
+            if !selected.is_empty() {
+                range = selected;
+            }
+        }
+
+        &self.entries[range.start]
+    }
+}
+
+enum BitCriteriaType {
+    Oxygen,
+    CO2,
+}
+
+fn get_product(width: usize, a: &Bits, b: &Bits) -> String {
+    (&a.to_biguint(width) * &b.to_biguint(width)).to_string()
+}
+
+fn p1(input: &str) -> String {
+    let report = Report::parse_input(input);
+    let (gamma_rate, epsilon_rate) = report.get_rates();
+    get_product(report.width, &gamma_rate, &epsilon_rate)
+}
+
+fn p2(input: &str) -> String {
+    let report = Report::parse_input(input);
+    let oxygen = report.find_entry_by_bit_criteria(BitCriteriaType::Oxygen);
+    let co2 = report.find_entry_by_bit_criteria(BitCriteriaType::CO2);
+    get_product(report.width, oxygen, co2)
+}
+
+pub fn solve_packed() {
+    println!("{}", p1(ACTUAL_INPUT));
+    println!("{}", p2(ACTUAL_INPUT));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = r"
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
+    #[test]
+    fn test_bits_order() {
+        let mut entries = ["1000", "0111", "0110"].map(Bits::parse);
+        entries.sort();
+        assert_eq!(entries, ["0110", "0111", "1000"].map(Bits::parse));
+    }
+
+    #[test]
+    fn test_wide_entries() {
+        // 70 columns, so the entries span two words
+        let wide = |suffix: &str| format!("1{}{}", "0".repeat(69 - suffix.len()), suffix);
+        let report = Report::parse_input(&[wide("1"), wide("11"), wide("10")].join("\n"));
+
+        assert_eq!(report.count_ones(0), 3);
+        assert_eq!(report.count_ones(68), 2);
+        assert_eq!(report.count_ones(69), 2);
+
+        let (gamma_rate, epsilon_rate) = report.get_rates();
+        assert_eq!(
+            gamma_rate.to_biguint(70).to_string(),
+            ((1u128 << 69) | 0b11).to_string()
+        );
+        assert_eq!(
+            epsilon_rate.to_biguint(70).to_string(),
+            ((1u128 << 69) - 1 - 0b11).to_string()
+        );
+    }
+
+    // the sample behind a 66 bit prefix of a one and then zeroes, so the
+    // products are wider than 128 bits
+    fn get_wide_sample() -> String {
+        SAMPLE_INPUT
+            .trim()
+            .lines()
+            .map(|line| format!("1{}{}", "0".repeat(65), line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_p1_wide() {
+        // (2^70 + 22) * ((2^65 - 1) * 2^5 + 9)
+        assert_eq!(
+            p1(&get_wide_sample()),
+            "1393796574908163946344801800419805182819846"
+        );
+    }
+
+    #[test]
+    fn test_p2_wide() {
+        // (2^70 + 23) * (2^70 + 10)
+        assert_eq!(
+            p2(&get_wide_sample()),
+            "1393796574908163946384941915524197167136998"
+        );
+    }
+
+    #[test]
+    fn test_same_bit_everywhere() {
+        // the leading zeroes can't narrow anything down
+        let input = SAMPLE_INPUT
+            .trim()
+            .lines()
+            .map(|line| format!("{}{}", "0".repeat(65), line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(p2(&input), "230");
+    }
+
+    #[test]
+    fn test_p1_sample() {
+        assert_eq!(p1(SAMPLE_INPUT), "198");
+    }
+
+    #[test]
+    fn test_p1_actual() {
+        assert_eq!(p1(ACTUAL_INPUT), "1082324");
+    }
+
+    #[test]
+    fn test_p2_sample() {
+        assert_eq!(p2(SAMPLE_INPUT), "230");
+    }
+
+    #[test]
+    fn test_p2_actual() {
+        assert_eq!(p2(ACTUAL_INPUT), "1353024");
+    }
+}
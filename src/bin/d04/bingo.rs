@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+pub fn parse_called_numbers(line: &str) -> Vec<i32> {
+    line.split(',')
+        .map(str::parse::<i32>)
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+}
+
+// the numbers of a square board, row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardLayout {
+    pub size: usize,
+    pub numbers: Vec<Vec<i32>>,
+}
+
+impl BoardLayout {
+    pub fn parse_from_lines(lines: Vec<&str>) -> Self {
+        let size = lines.len();
+
+        let numbers = lines
+            .into_iter()
+            .map(|line| {
+                line.split(' ')
+                    .filter(|x| !x.is_empty()) // input contains multiple spaces, these will show up as "" unless we remove them
+                    .map(str::parse::<i32>)
+                    .map(Result::unwrap)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        numbers.iter().enumerate().for_each(|(y, row)| {
+            if row.len() != size {
+                panic!(
+                    "Expected {}x{} board, found {} cols in row {}",
+                    size,
+                    size,
+                    row.len(),
+                    y
+                );
+            }
+        });
+
+        Self { size, numbers }
+    }
+
+    // (number, y, x) for every cell
+    pub fn cells(&self) -> impl Iterator<Item = (i32, usize, usize)> + '_ {
+        self.numbers.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, number)| (*number, y, x))
+        })
+    }
+}
+
+pub struct Input {
+    pub called_numbers: Vec<i32>,
+    pub boards: Vec<BoardLayout>,
+}
+
+impl Input {
+    // boards are separated by blank lines, and can be of any size
+    pub fn parse_input(input: &str) -> Self {
+        let mut lines = input.trim().lines();
+
+        let called_numbers = parse_called_numbers(lines.next().unwrap());
+
+        let boards = lines
+            .collect::<Vec<_>>()
+            .split(|line| line.trim().is_empty())
+            .filter(|board| !board.is_empty())
+            .map(|board| BoardLayout::parse_from_lines(board.to_vec()))
+            .collect();
+
+        Self {
+            called_numbers,
+            boards,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Line {
+    Row(usize),
+    Column(usize),
+    // top left to bottom right
+    Diagonal,
+    // top right to bottom left
+    AntiDiagonal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinCondition {
+    // this many lines completed
+    Lines(usize),
+    // every number marked
+    FullHouse,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub diagonals: bool,
+    pub win_condition: WinCondition,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            diagonals: false,
+            win_condition: WinCondition::Lines(1),
+        }
+    }
+}
+
+impl Rules {
+    // [--diagonals] [--lines <count> | --full-house]
+    pub fn parse_args(args: &[String]) -> Self {
+        let mut rules = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--diagonals" => rules.diagonals = true,
+                "--lines" => {
+                    let count = args
+                        .next()
+                        .unwrap_or_else(|| panic!("Missing value for {}", arg));
+                    rules.win_condition = WinCondition::Lines(count.parse().unwrap());
+                }
+                "--full-house" => rules.win_condition = WinCondition::FullHouse,
+                _ => panic!("Unknown rule {}", arg),
+            }
+        }
+
+        rules
+    }
+}
+
+pub struct Board {
+    layout: BoardLayout,
+    positions: HashMap<i32, (usize, usize)>,
+    marked: Vec<Vec<bool>>,
+    remaining: HashMap<Line, usize>,
+    completed: Vec<Line>,
+    unmarked_sum: i32,
+}
+
+impl Board {
+    pub fn new(layout: BoardLayout, rules: &Rules) -> Self {
+        let size = layout.size;
+
+        let mut lines = (0..size)
+            .flat_map(|i| [Line::Row(i), Line::Column(i)])
+            .collect::<Vec<_>>();
+        if rules.diagonals {
+            lines.extend([Line::Diagonal, Line::AntiDiagonal]);
+        }
+
+        Self {
+            positions: layout
+                .cells()
+                .map(|(number, y, x)| (number, (y, x)))
+                .collect(),
+            marked: vec![vec![false; size]; size],
+            remaining: lines.into_iter().map(|line| (line, size)).collect(),
+            completed: vec![],
+            unmarked_sum: layout.cells().map(|(number, _, _)| number).sum(),
+            layout,
+        }
+    }
+
+    fn get_lines(&self, y: usize, x: usize) -> Vec<Line> {
+        let mut lines = vec![Line::Row(y), Line::Column(x)];
+        if y == x {
+            lines.push(Line::Diagonal);
+        }
+        if y + x + 1 == self.layout.size {
+            lines.push(Line::AntiDiagonal);
+        }
+        lines
+    }
+
+    // the lines completed by this number
+    pub fn mark(&mut self, called_number: i32) -> Vec<Line> {
+        let Some(&(y, x)) = self.positions.get(&called_number) else {
+            return vec![];
+        };
+        if self.marked[y][x] {
+            return vec![];
+        }
+
+        self.marked[y][x] = true;
+        self.unmarked_sum -= called_number;
+
+        let completed = self
+            .get_lines(y, x)
+            .into_iter()
+            .filter(|line| match self.remaining.get_mut(line) {
+                Some(remaining) => {
+                    *remaining -= 1;
+                    *remaining == 0
+                }
+                // a diagonal, when they aren't played
+                None => false,
+            })
+            .collect::<Vec<_>>();
+        self.completed.extend(completed.iter().copied());
+        completed
+    }
+
+    pub fn has_won(&self, win_condition: &WinCondition) -> bool {
+        match win_condition {
+            WinCondition::Lines(count) => self.completed.len() >= *count,
+            WinCondition::FullHouse => self.marked.iter().flatten().all(|marked| *marked),
+        }
+    }
+
+    pub fn get_unmarked_sum(&self) -> i32 {
+        self.unmarked_sum
+    }
+
+    // marked numbers are in brackets
+    fn render(&self) -> String {
+        self.layout
+            .numbers
+            .iter()
+            .zip(self.marked.iter())
+            .map(|(numbers, marked)| {
+                numbers
+                    .iter()
+                    .zip(marked.iter())
+                    .map(|(number, marked)| {
+                        if *marked {
+                            format!("[{:>2}]", number)
+                        } else {
+                            format!(" {:>2} ", number)
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Win {
+    pub draw_index: usize,
+    pub called_number: i32,
+    pub board_index: usize,
+    // every completed line, in the order they were completed
+    pub lines: Vec<Line>,
+    pub unmarked_sum: i32,
+    // the board at the time of the win
+    pub rendered: String,
+}
+
+impl Win {
+    pub fn get_score(&self) -> i32 {
+        self.called_number * self.unmarked_sum
+    }
+}
+
+// plays the whole game, and returns every win in order; each board wins at
+// most once, and boards winning on the same draw are ordered by their index
+pub fn play(input: &Input, rules: &Rules) -> Vec<Win> {
+    let mut boards = input
+        .boards
+        .iter()
+        .map(|layout| Some(Board::new(layout.clone(), rules)))
+        .collect::<Vec<_>>();
+
+    input
+        .called_numbers
+        .iter()
+        .enumerate()
+        .flat_map(|(draw_index, called_number)| {
+            boards
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(board_index, slot)| {
+                    let board = slot.as_mut()?;
+                    board.mark(*called_number);
+
+                    if !board.has_won(&rules.win_condition) {
+                        return None;
+                    }
+
+                    let board = slot.take().unwrap();
+                    Some(Win {
+                        draw_index,
+                        called_number: *called_number,
+                        board_index,
+                        lines: board.completed.clone(),
+                        unmarked_sum: board.unmarked_sum,
+                        rendered: board.render(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = r"
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn test_parse_args() {
+        let args = |args: &str| {
+            args.split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(Rules::parse_args(&[]), Rules::default());
+        assert_eq!(
+            Rules::parse_args(&args("--diagonals --lines 2")),
+            Rules {
+                diagonals: true,
+                win_condition: WinCondition::Lines(2)
+            }
+        );
+        assert_eq!(
+            Rules::parse_args(&args("--full-house")).win_condition,
+            WinCondition::FullHouse
+        );
+    }
+
+    #[test]
+    fn test_play() {
+        let wins = play(&Input::parse_input(SAMPLE_INPUT), &Rules::default());
+
+        assert_eq!(
+            wins.iter()
+                .map(|win| (win.draw_index, win.board_index))
+                .collect::<Vec<_>>(),
+            vec![(11, 2), (13, 0), (14, 1)]
+        );
+        assert_eq!(wins[0].lines, vec![Line::Row(0)]);
+        assert_eq!(wins[0].get_score(), 4512);
+        assert_eq!(wins[2].get_score(), 1924);
+        assert!(wins[0]
+            .rendered
+            .starts_with("[14][21][17][24][ 4]\n 10  16  15 [ 9] 19 "));
+    }
+
+    #[test]
+    fn test_diagonals_and_sizes() {
+        let input = Input::parse_input("1,5,9,4\n\n1 2 3\n4 5 6\n7 8 9\n\n1 2\n3 4");
+
+        // neither board completes a row or a column
+        let wins = play(&input, &Rules::default());
+        assert_eq!(wins, vec![]);
+
+        let wins = play(
+            &input,
+            &Rules {
+                diagonals: true,
+                ..Rules::default()
+            },
+        );
+        assert_eq!(
+            wins.iter()
+                .map(|win| (win.draw_index, win.board_index, win.lines.clone()))
+                .collect::<Vec<_>>(),
+            vec![(2, 0, vec![Line::Diagonal]), (3, 1, vec![Line::Diagonal])]
+        );
+    }
+
+    #[test]
+    fn test_win_conditions() {
+        let input = Input::parse_input("1,2,3,4\n\n1 2\n3 4");
+
+        let wins = play(
+            &input,
+            &Rules {
+                diagonals: false,
+                win_condition: WinCondition::Lines(2),
+            },
+        );
+        assert_eq!(wins[0].draw_index, 2);
+        assert_eq!(wins[0].lines, vec![Line::Row(0), Line::Column(0)]);
+
+        let wins = play(
+            &input,
+            &Rules {
+                diagonals: false,
+                win_condition: WinCondition::FullHouse,
+            },
+        );
+        assert_eq!(wins[0].draw_index, 3);
+        assert_eq!(wins[0].unmarked_sum, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_not_square() {
+        BoardLayout::parse_from_lines(vec!["1 2", "3"]);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::bingo::{play, Input, Line, Rules, Win};
+
+const ACTUAL_INPUT: &str = include_str!("input.txt");
+
+fn format_line(line: &Line) -> String {
+    match line {
+        Line::Row(y) => format!("row {}", y),
+        Line::Column(x) => format!("column {}", x),
+        Line::Diagonal => "diagonal".to_string(),
+        Line::AntiDiagonal => "anti-diagonal".to_string(),
+    }
+}
+
+pub fn format_win(position: usize, win: &Win) -> String {
+    format!(
+        "#{} board {} wins on draw {} (number {}) with {}, score {}\n{}",
+        position + 1,
+        win.board_index,
+        win.draw_index,
+        win.called_number,
+        win.lines
+            .iter()
+            .map(format_line)
+            .collect::<Vec<_>>()
+            .join(", "),
+        win.get_score(),
+        win.rendered
+    )
+}
+
+fn p1(input: &str) -> String {
+    play(&Input::parse_input(input), &Rules::default())
+        .first()
+        .expect("Cannot find a winning board")
+        .get_score()
+        .to_string()
+}
+
+fn p2(input: &str) -> String {
+    play(&Input::parse_input(input), &Rules::default())
+        .last()
+        .expect("Cannot find a last losing board")
+        .get_score()
+        .to_string()
+}
+
+pub fn replay_engine(rules: &Rules) {
+    play(&Input::parse_input(ACTUAL_INPUT), rules)
+        .iter()
+        .enumerate()
+        .for_each(|(position, win)| println!("{}\n", format_win(position, win)));
+}
+
+// the k-th board to win, counting from 1
+pub fn rank_engine(k: usize, rules: &Rules) {
+    let wins = play(&Input::parse_input(ACTUAL_INPUT), rules);
+    match k.checked_sub(1).and_then(|position| wins.get(position)) {
+        Some(win) => println!("{}", format_win(k - 1, win)),
+        None => println!("Only {} boards win", wins.len()),
+    }
+}
+
+pub fn solve_engine() {
+    println!("{}", p1(ACTUAL_INPUT));
+    println!("{}", p2(ACTUAL_INPUT));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = r"
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn test_format_win() {
+        let wins = play(&Input::parse_input(SAMPLE_INPUT), &Rules::default());
+        assert!(format_win(0, &wins[0])
+            .starts_with("#1 board 2 wins on draw 11 (number 24) with row 0, score 4512\n"));
+    }
+
+    #[test]
+    fn test_p1_sample() {
+        assert_eq!(p1(SAMPLE_INPUT), "4512");
+    }
+
+    #[test]
+    fn test_p1_actual() {
+        assert_eq!(p1(ACTUAL_INPUT), "50008");
+    }
+
+    #[test]
+    fn test_p2_sample() {
+        assert_eq!(p2(SAMPLE_INPUT), "1924");
+    }
+
+    #[test]
+    fn test_p2_actual() {
+        assert_eq!(p2(ACTUAL_INPUT), "17408");
+    }
+}
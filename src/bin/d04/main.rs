@@ -1,11 +1,36 @@
+mod bingo;
+mod engine;
 mod original;
 mod shortcircuit;
 
+use bingo::Rules;
+
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    match args.get(1).map(String::as_str) {
+        // cargo run --bin d04 -- replay [--diagonals] [--lines <count> | --full-house]
+        Some("replay") => {
+            engine::replay_engine(&Rules::parse_args(&args[2..]));
+            return;
+        }
+        // cargo run --bin d04 -- rank <k> [--diagonals] [--lines <count> | --full-house]
+        Some("rank") => {
+            let k = args.get(2).expect("Missing rank").parse().unwrap();
+            engine::rank_engine(k, &Rules::parse_args(&args[3..]));
+            return;
+        }
+        _ => {}
+    }
+
     println!("Original:");
     original::main_original();
 
     println!();
     println!("Short-circuit:");
     shortcircuit::main_shortcircuit();
+
+    println!();
+    println!("Engine:");
+    engine::solve_engine();
 }
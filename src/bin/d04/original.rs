@@ -1,18 +1,6 @@
-use std::{collections::HashMap, iter};
+use crate::bingo::{Board, BoardLayout, Input, Rules};
 
 const ACTUAL_INPUT: &str = include_str!("input.txt");
-const BOARD_SIZE: i32 = 5;
-
-fn parse_called_numbers(line: &str) -> Vec<i32> {
-    line.split(',')
-        .map(str::parse::<i32>)
-        .map(Result::unwrap)
-        .collect::<Vec<_>>()
-}
-
-struct Board {
-    coords: HashMap<i32, (i32, i32)>,
-}
 
 struct BoardWinState {
     called_index: i32,
@@ -20,118 +8,24 @@ struct BoardWinState {
     unmarked_numbers_sum: i32,
 }
 
-impl Board {
-    fn parse_from_lines(lines: Vec<&str>) -> Self {
-        if lines.len() != BOARD_SIZE as usize {
-            panic!("Expected 5x5 board, found {} rows", lines.len());
-        }
-
-        let mut coords = HashMap::new();
-
-        lines
-            .into_iter()
-            .map(|line| {
-                line.split(' ')
-                    .filter(|x| !x.is_empty()) // input contains multiple spaces, these will show up as "" unless we remove them
-                    .map(str::parse::<i32>)
-                    .map(Result::unwrap)
-                    .collect::<Vec<_>>()
-            })
-            .enumerate()
-            .for_each(|(y, rows)| {
-                if rows.len() != BOARD_SIZE as usize {
-                    panic!("Expected 5x5 board, found {} cols in row {}", rows.len(), y);
-                }
-
-                rows.into_iter().enumerate().for_each(|(x, number)| {
-                    coords.insert(number, (y as i32, x as i32));
-                });
-            });
-
-        Self { coords }
-    }
-
-    fn get_win_state(&self, called_numbers: &[i32]) -> Option<BoardWinState> {
-        let mut rows_remaining = iter::repeat(BOARD_SIZE)
-            .take(BOARD_SIZE as usize)
-            .collect::<Vec<_>>();
-        let mut cols_remaining = iter::repeat(BOARD_SIZE)
-            .take(BOARD_SIZE as usize)
-            .collect::<Vec<_>>();
-
-        let mut remaining_numbers = self.coords.clone();
-
-        called_numbers
-            .iter()
-            .enumerate()
-            .find_map(|(index, number)| match remaining_numbers.remove(number) {
-                None => None,
-                Some(number_coord) => {
-                    {
-                        let y = number_coord.0 as usize;
-                        rows_remaining[y] -= 1;
-
-                        if rows_remaining[y] == 0 {
-                            return Some(BoardWinState {
-                                called_index: index as i32,
-                                called_number: *number,
-                                unmarked_numbers_sum: remaining_numbers.keys().into_iter().sum(),
-                            });
-                        }
-                    }
-
-                    {
-                        let x = number_coord.1 as usize;
-                        cols_remaining[x] -= 1;
-                        if cols_remaining[x] == 0 {
-                            return Some(BoardWinState {
-                                called_index: index as i32,
-                                called_number: *number,
-                                unmarked_numbers_sum: remaining_numbers.keys().into_iter().sum(),
-                            });
-                        }
-                    }
-
-                    None
-                }
-            })
-    }
-}
-
-struct Input {
-    called_numbers: Vec<i32>,
-    boards: Vec<Board>,
-}
-
-impl Input {
-    fn parse_input(input: &str) -> Self {
-        let mut lines = input.trim().lines();
-
-        let called_numbers = parse_called_numbers(lines.next().unwrap());
-
-        let mut boards = vec![];
-
-        loop {
-            lines.next();
-
-            let current_board = (0..BOARD_SIZE)
-                .map(|_| lines.next())
-                .flatten()
-                .collect::<Vec<_>>();
-
-            if current_board.is_empty() {
-                // we finish parsing
-                break;
+// every board is played on its own, until a number completes its first line
+fn get_win_state(layout: &BoardLayout, called_numbers: &[i32]) -> Option<BoardWinState> {
+    let mut board = Board::new(layout.clone(), &Rules::default());
+
+    called_numbers
+        .iter()
+        .enumerate()
+        .find_map(|(index, number)| {
+            if board.mark(*number).is_empty() {
+                None
+            } else {
+                Some(BoardWinState {
+                    called_index: index as i32,
+                    called_number: *number,
+                    unmarked_numbers_sum: board.get_unmarked_sum(),
+                })
             }
-
-            boards.push(Board::parse_from_lines(current_board));
-        }
-
-        Self {
-            called_numbers,
-            boards,
-        }
-    }
+        })
 }
 
 fn p1(input: &str) -> String {
@@ -139,8 +33,8 @@ fn p1(input: &str) -> String {
 
     let result = input
         .boards
-        .into_iter()
-        .flat_map(|board| board.get_win_state(&input.called_numbers))
+        .iter()
+        .flat_map(|layout| get_win_state(layout, &input.called_numbers))
         .min_by(|x, y| x.called_index.cmp(&y.called_index))
         .expect("Cannot find a winning board");
 
@@ -152,8 +46,8 @@ fn p2(input: &str) -> String {
 
     let result = input
         .boards
-        .into_iter()
-        .flat_map(|board| board.get_win_state(&input.called_numbers))
+        .iter()
+        .flat_map(|layout| get_win_state(layout, &input.called_numbers))
         .max_by(|x, y| x.called_index.cmp(&y.called_index))
         .expect("Cannot find a last losing board");
 
@@ -1,157 +1,29 @@
-use std::{collections::HashMap, iter};
+use crate::bingo::{Board, Input, Rules};
 
 const ACTUAL_INPUT: &str = include_str!("input.txt");
-const BOARD_SIZE: i32 = 5;
 
-fn parse_called_numbers(line: &str) -> Vec<i32> {
-    line.split(',')
-        .map(str::parse::<i32>)
-        .map(Result::unwrap)
-        .collect::<Vec<_>>()
-}
-
-struct Board {
-    remaining_numbers: HashMap<i32, (i32, i32)>,
-    remaining_numbers_sum: i32,
-
-    rows_remaining: Vec<i32>,
-    cols_remaining: Vec<i32>,
-    won: bool,
-}
-
-impl Board {
-    fn parse_from_lines(lines: Vec<&str>) -> Self {
-        if lines.len() != BOARD_SIZE as usize {
-            panic!("Expected 5x5 board, found {} rows", lines.len());
-        }
-
-        let mut coords = HashMap::new();
-
-        lines
-            .into_iter()
-            .map(|line| {
-                line.split(' ')
-                    .filter(|x| !x.is_empty()) // input contains multiple spaces, these will show up as "" unless we remove them
-                    .map(str::parse::<i32>)
-                    .map(Result::unwrap)
-                    .collect::<Vec<_>>()
-            })
-            .enumerate()
-            .for_each(|(y, rows)| {
-                if rows.len() != BOARD_SIZE as usize {
-                    panic!("Expected 5x5 board, found {} cols in row {}", rows.len(), y);
-                }
-
-                rows.into_iter().enumerate().for_each(|(x, number)| {
-                    coords.insert(number, (y as i32, x as i32));
-                });
-            });
-
-        let sum = coords.keys().sum();
-        let rows_remaining = iter::repeat(BOARD_SIZE)
-            .take(BOARD_SIZE as usize)
-            .collect::<Vec<_>>();
-        let cols_remaining = iter::repeat(BOARD_SIZE)
-            .take(BOARD_SIZE as usize)
-            .collect::<Vec<_>>();
-
-        Self {
-            remaining_numbers: coords,
-            remaining_numbers_sum: sum,
-            rows_remaining,
-            cols_remaining,
-            won: false,
-        }
-    }
-
-    fn update(&mut self, called_number: i32) -> bool {
-        if self.won {
-            panic!("We already won but still cancelling numbers");
-        }
-
-        if let Some(number_coord) = self.remaining_numbers.remove(&called_number) {
-            self.remaining_numbers_sum -= called_number;
-
-            {
-                let y = number_coord.0 as usize;
-                self.rows_remaining[y] -= 1;
-
-                if self.rows_remaining[y] == 0 {
-                    self.won = true;
-                    return true;
-                }
-            }
-
-            {
-                let x = number_coord.1 as usize;
-                self.cols_remaining[x] -= 1;
-                if self.cols_remaining[x] == 0 {
-                    self.won = true;
-                    return true;
-                }
-            }
-        }
-
-        false
-    }
-
-    fn get_remaining_sum(&self) -> i32 {
-        self.remaining_numbers_sum
-    }
-
-    fn get_is_won(&self) -> bool {
-        self.won
-    }
-}
-
-struct Input {
-    called_numbers: Vec<i32>,
-    boards: Vec<Board>,
-}
-
-impl Input {
-    fn parse_input(input: &str) -> Self {
-        let mut lines = input.trim().lines();
-
-        let called_numbers = parse_called_numbers(lines.next().unwrap());
-
-        let mut boards = vec![];
-
-        loop {
-            lines.next();
-
-            let current_board = (0..BOARD_SIZE)
-                .map(|_| lines.next())
-                .flatten()
-                .collect::<Vec<_>>();
-
-            if current_board.is_empty() {
-                // we finish parsing
-                break;
-            }
-
-            boards.push(Board::parse_from_lines(current_board));
-        }
-
-        Self {
-            called_numbers,
-            boards,
-        }
-    }
+fn create_boards(input: &Input) -> Vec<Board> {
+    input
+        .boards
+        .iter()
+        .map(|layout| Board::new(layout.clone(), &Rules::default()))
+        .collect()
 }
 
+// stops at the first number that completes a line on any board
 fn p1(input: &str) -> String {
-    let mut input = Input::parse_input(input);
+    let input = Input::parse_input(input);
+    let mut boards = create_boards(&input);
 
     input
         .called_numbers
         .into_iter()
         .find_map(|called_number| {
-            input.boards.iter_mut().find_map(|board| {
-                if board.update(called_number) {
-                    Some(called_number * board.get_remaining_sum())
-                } else {
+            boards.iter_mut().find_map(|board| {
+                if board.mark(called_number).is_empty() {
                     None
+                } else {
+                    Some(called_number * board.get_unmarked_sum())
                 }
             })
         })
@@ -159,50 +31,29 @@ fn p1(input: &str) -> String {
         .to_string()
 }
 
+// drops every board as soon as it wins, until only the last one is left
 fn p2(input: &str) -> String {
     let input = Input::parse_input(input);
+    let mut boards = create_boards(&input);
 
-    let (board, calling_number) =
-        input
-            .called_numbers
-            .into_iter()
-            .fold((input.boards, None), |result, called_number| {
-                if result.1.is_some() {
-                    result
-                } else if result.0.len() == 1 {
-                    let boards = result
-                        .0
-                        .into_iter()
-                        .map(|mut board| {
-                            board.update(called_number);
-                            board
-                        })
-                        .collect::<Vec<_>>();
-
-                    let last_number = if boards[0].get_is_won() {
-                        Some(called_number)
-                    } else {
-                        None
-                    };
-
-                    (boards, last_number)
+    input
+        .called_numbers
+        .into_iter()
+        .find_map(|called_number| {
+            if boards.len() == 1 {
+                let board = &mut boards[0];
+                if board.mark(called_number).is_empty() {
+                    None
                 } else {
-                    (
-                        result
-                            .0
-                            .into_iter()
-                            .map(|mut board| {
-                                board.update(called_number);
-                                board
-                            })
-                            .filter(|board| !board.get_is_won())
-                            .collect::<Vec<_>>(),
-                        None,
-                    )
+                    Some(called_number * board.get_unmarked_sum())
                 }
-            });
-
-    (calling_number.unwrap() * board[0].get_remaining_sum()).to_string()
+            } else {
+                boards.retain_mut(|board| board.mark(called_number).is_empty());
+                None
+            }
+        })
+        .expect("Cannot find a last losing board")
+        .to_string()
 }
 
 pub fn main_shortcircuit() {
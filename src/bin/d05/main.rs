@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+mod sweep;
+
+use advent_of_code_2021_rust::ppm;
 
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
@@ -45,7 +47,7 @@ impl Line {
         );
 
         LineIterator {
-            current: (self.start.0 - direction.0, self.start.1 - direction.1),
+            current: Some(self.start),
             end: self.end,
             direction,
         }
@@ -53,7 +55,8 @@ impl Line {
 }
 
 struct LineIterator {
-    current: (i32, i32),
+    // None once the end has been returned
+    current: Option<(i32, i32)>,
     end: (i32, i32),
     direction: (i32, i32),
 }
@@ -62,32 +65,108 @@ impl Iterator for LineIterator {
     type Item = (i32, i32);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current != self.end {
-            self.current.0 += self.direction.0;
-            self.current.1 += self.direction.1;
-            Some(self.current)
+        let current = self.current?;
+
+        self.current = if current != self.end {
+            Some((current.0 + self.direction.0, current.1 + self.direction.1))
         } else {
             None
-        }
+        };
+
+        Some(current)
     }
 }
 
-fn solve(input: &str, include_diagonals: bool) -> String {
+fn parse_lines(input: &str, include_diagonals: bool) -> Vec<Line> {
     input
         .trim()
         .lines()
         .map(Line::parse_from_line)
         .filter(|line| include_diagonals || line.is_horizontal_or_vertical())
-        .fold(&mut HashMap::new(), |visited, current_line| {
-            current_line.iter().for_each(|coord| {
-                *visited.entry(coord).or_insert(0) += 1;
-            });
-            visited
-        })
-        .values()
-        .into_iter()
-        .filter(|x| **x > 1)
-        .count()
+        .collect()
+}
+
+// how many lines cover every point, from the origin (as in the puzzle's
+// diagram) to the furthest line end
+#[derive(Debug, PartialEq, Eq)]
+struct VentMap {
+    min: (i32, i32),
+    width: usize,
+    height: usize,
+    counts: Vec<u32>,
+}
+
+impl VentMap {
+    fn from_lines(lines: &[Line]) -> Self {
+        let ends = || lines.iter().flat_map(|line| [line.start, line.end]);
+        let min = (
+            ends().map(|coord| coord.0).min().unwrap_or(0).min(0),
+            ends().map(|coord| coord.1).min().unwrap_or(0).min(0),
+        );
+        let max = (
+            ends().map(|coord| coord.0).max().unwrap_or(0).max(0),
+            ends().map(|coord| coord.1).max().unwrap_or(0).max(0),
+        );
+
+        let width = (max.0 - min.0 + 1) as usize;
+        let height = (max.1 - min.1 + 1) as usize;
+        let mut counts = vec![0; width * height];
+
+        lines.iter().flat_map(Line::iter).for_each(|(x, y)| {
+            counts[(y - min.1) as usize * width + (x - min.0) as usize] += 1;
+        });
+
+        Self {
+            min,
+            width,
+            height,
+            counts,
+        }
+    }
+
+    fn count_overlaps(&self) -> usize {
+        self.counts.iter().filter(|count| **count > 1).count()
+    }
+
+    // '.' where there's no line, and '+' for more than 9 lines
+    fn render_digits(&self) -> String {
+        self.counts
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|count| match count {
+                        0 => '.',
+                        1..=9 => char::from_digit(*count, 10).unwrap(),
+                        _ => '+',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // black where there's no line, then brighter for every extra line
+    fn render_ppm(&self, scale: usize) -> Vec<u8> {
+        let max = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        let pixels = self
+            .counts
+            .iter()
+            .map(|count| {
+                if *count == 0 {
+                    [0, 0, 0]
+                } else {
+                    let level = (55 + 200 * count / max) as u8;
+                    [level, level / 2, 0]
+                }
+            })
+            .collect::<Vec<_>>();
+        ppm::encode_scaled(self.width, self.height, &pixels, scale)
+    }
+}
+
+fn solve(input: &str, include_diagonals: bool) -> String {
+    VentMap::from_lines(&parse_lines(input, include_diagonals))
+        .count_overlaps()
         .to_string()
 }
 
@@ -100,8 +179,38 @@ fn p2(input: &str) -> String {
 }
 
 fn main() {
-    println!("{}", p1(ACTUAL_INPUT));
-    println!("{}", p2(ACTUAL_INPUT));
+    let args = std::env::args().collect::<Vec<_>>();
+    // diagonals are included unless asked otherwise
+    let include_diagonals = !args.iter().any(|arg| arg == "--no-diagonals");
+
+    match args.get(1).map(String::as_str) {
+        // cargo run --bin d05 -- map [--no-diagonals]
+        Some("map") => {
+            let vent_map = VentMap::from_lines(&parse_lines(ACTUAL_INPUT, include_diagonals));
+            println!("{}", vent_map.render_digits());
+        }
+        // cargo run --bin d05 -- ppm <file> [scale] [--no-diagonals]
+        Some("ppm") => {
+            let file = args.get(2).expect("Missing file");
+            let scale = args
+                .get(3)
+                .filter(|arg| !arg.starts_with("--"))
+                .map_or(1, |scale| scale.parse().unwrap());
+            let vent_map = VentMap::from_lines(&parse_lines(ACTUAL_INPUT, include_diagonals));
+            std::fs::write(file, vent_map.render_ppm(scale)).unwrap();
+        }
+        // cargo run --bin d05 -- sweep [--no-diagonals]
+        Some("sweep") => {
+            println!(
+                "{}",
+                sweep::count_overlaps(&parse_lines(ACTUAL_INPUT, include_diagonals))
+            );
+        }
+        _ => {
+            println!("{}", p1(ACTUAL_INPUT));
+            println!("{}", p2(ACTUAL_INPUT));
+        }
+    }
 }
 
 #[cfg(test)]
@@ -121,6 +230,55 @@ mod tests {
 5,5 -> 8,2
 ";
 
+    #[test]
+    fn test_vent_map() {
+        let vent_map = VentMap::from_lines(&parse_lines(SAMPLE_INPUT, true));
+
+        assert_eq!(
+            vent_map.render_digits(),
+            r"1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111...."
+        );
+        assert_eq!(vent_map.count_overlaps(), 12);
+    }
+
+    #[test]
+    fn test_vent_map_negative() {
+        let vent_map = VentMap::from_lines(&parse_lines("-1,1 -> 1,1\n0,0 -> 0,2", true));
+
+        assert_eq!(vent_map.render_digits(), ".1.\n121\n.1.");
+        assert_eq!(vent_map.count_overlaps(), 1);
+    }
+
+    #[test]
+    fn test_line_iter() {
+        let line = Line::parse_from_line("3,1 -> 1,3");
+        assert_eq!(
+            line.iter().collect::<Vec<_>>(),
+            vec![(3, 1), (2, 2), (1, 3)]
+        );
+
+        let point = Line::parse_from_line("2,2 -> 2,2");
+        assert_eq!(point.iter().collect::<Vec<_>>(), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_render_ppm() {
+        let vent_map = VentMap::from_lines(&parse_lines("0,0 -> 1,0", true));
+        assert_eq!(
+            vent_map.render_ppm(1),
+            ppm::encode(2, 1, [[255, 127, 0], [255, 127, 0]])
+        );
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(p1(SAMPLE_INPUT), "5");
@@ -0,0 +1,407 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use super::Line;
+
+// every line lies on one of four families of parallel lines; within a
+// family, a line is a key (which of the parallel lines) and a range of
+// parameters along it. Keys and parameters are i64, since y + x and the
+// ends of the ranges can overflow i32
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Family {
+    // key y, parameter x
+    Horizontal,
+    // key x, parameter y
+    Vertical,
+    // key y - x, parameter x
+    Diagonal,
+    // key y + x, parameter x
+    AntiDiagonal,
+}
+
+const FAMILIES: [Family; 4] = [
+    Family::Horizontal,
+    Family::Vertical,
+    Family::Diagonal,
+    Family::AntiDiagonal,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Segment {
+    family: Family,
+    key: i64,
+    // inclusive
+    from: i64,
+    to: i64,
+}
+
+impl Segment {
+    fn from_line(line: &Line) -> Self {
+        let (x1, y1) = (line.start.0 as i64, line.start.1 as i64);
+        let (x2, y2) = (line.end.0 as i64, line.end.1 as i64);
+
+        let (family, key, from, to) = if y1 == y2 {
+            (Family::Horizontal, y1, x1, x2)
+        } else if x1 == x2 {
+            (Family::Vertical, x1, y1, y2)
+        } else if y2 - y1 == x2 - x1 {
+            (Family::Diagonal, y1 - x1, x1, x2)
+        } else if y2 - y1 == x1 - x2 {
+            (Family::AntiDiagonal, y1 + x1, x1, x2)
+        } else {
+            panic!(
+                "Expected a line at a multiple of 45 degrees, found {:?}",
+                line
+            );
+        };
+
+        Self {
+            family,
+            key,
+            from: from.min(to),
+            to: from.max(to),
+        }
+    }
+
+    // (key, parameter) of a point in this family
+    fn project(family: Family, (x, y): (i64, i64)) -> (i64, i64) {
+        match family {
+            Family::Horizontal => (y, x),
+            Family::Vertical => (x, y),
+            Family::Diagonal => (y - x, x),
+            Family::AntiDiagonal => (y + x, x),
+        }
+    }
+
+    // the point at this parameter
+    fn unproject(&self, parameter: i64) -> (i64, i64) {
+        match self.family {
+            Family::Horizontal => (parameter, self.key),
+            Family::Vertical => (self.key, parameter),
+            Family::Diagonal => (parameter, self.key + parameter),
+            Family::AntiDiagonal => (parameter, self.key - parameter),
+        }
+    }
+
+    // the keys of another family this segment goes through, as a range
+    // (only every other key between a diagonal and an anti-diagonal)
+    fn get_key_range(&self, family: Family) -> (i64, i64) {
+        let from = Self::project(family, self.unproject(self.from)).0;
+        let to = Self::project(family, self.unproject(self.to)).0;
+        (from.min(to), from.max(to))
+    }
+
+    fn len(&self) -> usize {
+        (self.to - self.from + 1) as usize
+    }
+}
+
+// where the (infinite) lines with these keys in two different families
+// cross, if it's on a whole point
+fn get_crossing(family_a: Family, a: i64, family_b: Family, b: i64) -> Option<(i64, i64)> {
+    use Family::*;
+
+    match (family_a, family_b) {
+        (Horizontal, Vertical) => Some((b, a)),
+        (Horizontal, Diagonal) => Some((a - b, a)),
+        (Horizontal, AntiDiagonal) => Some((b - a, a)),
+        (Vertical, Diagonal) => Some((a, a + b)),
+        (Vertical, AntiDiagonal) => Some((a, b - a)),
+        (Diagonal, AntiDiagonal) => {
+            if (b - a) % 2 == 0 {
+                Some(((b - a) / 2, (a + b) / 2))
+            } else {
+                None
+            }
+        }
+        (x, y) if x == y => None,
+        _ => get_crossing(family_b, b, family_a, a),
+    }
+}
+
+// sweeps along every key of every family, and returns the segments covered
+// by at least one line and by at least two lines, which are disjoint (and
+// sorted) along each key
+fn sweep_family(segments: &[Segment]) -> (Vec<Segment>, Vec<Segment>) {
+    let mut by_key = HashMap::<(Family, i64), Vec<(i64, i32)>>::new();
+    segments.iter().for_each(|segment| {
+        // +1 where a segment starts, -1 just past its end
+        let events = by_key.entry((segment.family, segment.key)).or_default();
+        events.push((segment.from, 1));
+        events.push((segment.to + 1, -1));
+    });
+
+    let mut covered = vec![];
+    let mut overlapping = vec![];
+
+    by_key.into_iter().for_each(|((family, key), mut events)| {
+        events.sort();
+
+        let mut depth = 0;
+        let mut previous = 0;
+        events.into_iter().for_each(|(parameter, delta)| {
+            if parameter > previous {
+                let segment = Segment {
+                    family,
+                    key,
+                    from: previous,
+                    to: parameter - 1,
+                };
+                if depth >= 1 {
+                    covered.push(segment);
+                }
+                if depth >= 2 {
+                    overlapping.push(segment);
+                }
+            }
+            depth += delta;
+            previous = parameter;
+        });
+    });
+
+    (covered, overlapping)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    // ordered so that, at the same key, segments ending just before it are
+    // gone and segments starting on it are in, before anything is queried
+    Remove(i64),
+    Insert(i64),
+    // every active key in this inclusive range crosses the query's key
+    Query(i64, i64),
+}
+
+// every point where a covered segment of family `a` crosses one of family
+// `b`. In the plane of (key in a, key in b), the segments of `a` run along
+// the keys of `b` and the other way around, so this sweeps along the keys
+// of `b` and keeps the keys of the segments of `a` being crossed
+fn sweep_crossings(a: &[Segment], b: &[Segment], crossings: &mut HashSet<(i64, i64)>) {
+    let (Some(family_a), Some(family_b)) =
+        (a.first().map(|s| s.family), b.first().map(|s| s.family))
+    else {
+        return;
+    };
+
+    let mut events = a
+        .iter()
+        .flat_map(|segment| {
+            let (from, to) = segment.get_key_range(family_b);
+            [
+                (from, Event::Insert(segment.key)),
+                (to + 1, Event::Remove(segment.key)),
+            ]
+        })
+        .chain(b.iter().map(|segment| {
+            let (from, to) = segment.get_key_range(family_a);
+            (segment.key, Event::Query(from, to))
+        }))
+        .collect::<Vec<_>>();
+    events.sort_unstable();
+
+    // the covered segments of a key are disjoint, so a key is in at most
+    // once at any point of the sweep
+    let mut active = BTreeSet::new();
+    events.into_iter().for_each(|(key_b, event)| match event {
+        Event::Remove(key_a) => {
+            active.remove(&key_a);
+        }
+        Event::Insert(key_a) => {
+            active.insert(key_a);
+        }
+        Event::Query(from, to) => {
+            crossings.extend(
+                active
+                    .range(from..=to)
+                    .filter_map(|key_a| get_crossing(family_a, *key_a, family_b, key_b)),
+            );
+        }
+    });
+}
+
+// the number of points covered by at least two lines, without rasterising
+// the lines: overlaps of parallel lines come from a sweep along each line,
+// and everything else is a crossing of lines from different families, found
+// by a sweep for every pair of families
+pub fn count_overlaps(lines: &[Line]) -> usize {
+    let segments = lines.iter().map(Segment::from_line).collect::<Vec<_>>();
+    let (covered, overlapping) = sweep_family(&segments);
+
+    let by_family = FAMILIES.map(|family| {
+        covered
+            .iter()
+            .filter(|segment| segment.family == family)
+            .copied()
+            .collect::<Vec<_>>()
+    });
+
+    let mut crossings = HashSet::new();
+    (0..4).for_each(|i| {
+        ((i + 1)..4).for_each(|j| sweep_crossings(&by_family[i], &by_family[j], &mut crossings))
+    });
+
+    // the overlaps along every key, sorted
+    let mut overlaps_by_key = HashMap::<(Family, i64), Vec<(i64, i64)>>::new();
+    overlapping.iter().for_each(|segment| {
+        overlaps_by_key
+            .entry((segment.family, segment.key))
+            .or_default()
+            .push((segment.from, segment.to));
+    });
+    overlaps_by_key
+        .values_mut()
+        .for_each(|ranges| ranges.sort_unstable());
+
+    let is_in_overlap = |family: Family, point: (i64, i64)| {
+        let (key, parameter) = Segment::project(family, point);
+        overlaps_by_key.get(&(family, key)).is_some_and(|ranges| {
+            let i = ranges.partition_point(|(_, to)| *to < parameter);
+            ranges.get(i).is_some_and(|(from, _)| *from <= parameter)
+        })
+    };
+
+    let overlapping_total = overlapping.iter().map(Segment::len).sum::<usize>();
+
+    // a crossing is counted once, but it may also be in the overlaps of
+    // several families, which counted it once each
+    crossings
+        .into_iter()
+        .fold(overlapping_total, |total, point| {
+            let in_overlaps = FAMILIES
+                .iter()
+                .filter(|family| is_in_overlap(**family, point))
+                .count();
+            if in_overlaps == 0 {
+                total + 1
+            } else {
+                total - (in_overlaps - 1)
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, VentMap};
+    use advent_of_code_2021_rust::rng::Rng;
+
+    const SAMPLE_INPUT: &str = r"
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+    fn generate_line(rng: &mut Rng) -> Line {
+        let start = (
+            rng.next_in_range(-5, 14) as i32,
+            rng.next_in_range(-5, 14) as i32,
+        );
+        let length = rng.below(12) as i32;
+        let direction = [(1, 0), (0, 1), (1, 1), (1, -1), (-1, 0), (-1, -1)][rng.below(6) as usize];
+        Line {
+            start,
+            end: (
+                start.0 + direction.0 * length,
+                start.1 + direction.1 * length,
+            ),
+        }
+    }
+
+    fn parse(input: &str) -> Vec<Line> {
+        input.lines().map(Line::parse_from_line).collect()
+    }
+
+    #[test]
+    fn test_get_crossing() {
+        let segments = parse("0,3 -> 9,3\n2,0 -> 2,9\n0,0 -> 9,9\n0,9 -> 9,0\n0,8 -> 8,0")
+            .iter()
+            .map(Segment::from_line)
+            .collect::<Vec<_>>();
+        let crossing = |i: usize, j: usize| {
+            get_crossing(
+                segments[i].family,
+                segments[i].key,
+                segments[j].family,
+                segments[j].key,
+            )
+        };
+
+        assert_eq!(crossing(0, 1), Some((2, 3)));
+        assert_eq!(crossing(1, 0), Some((2, 3)));
+        assert_eq!(crossing(0, 2), Some((3, 3)));
+        assert_eq!(crossing(1, 3), Some((2, 7)));
+        assert_eq!(crossing(3, 2), None);
+        assert_eq!(crossing(4, 2), Some((4, 4)));
+        assert_eq!(crossing(3, 4), None);
+    }
+
+    #[test]
+    fn test_count_overlaps_sample() {
+        assert_eq!(count_overlaps(&parse_lines(SAMPLE_INPUT, false)), 5);
+        assert_eq!(count_overlaps(&parse_lines(SAMPLE_INPUT, true)), 12);
+    }
+
+    #[test]
+    fn test_count_overlaps_random() {
+        let mut rng = Rng::new(0x2021_0005);
+
+        (0..300).for_each(|_| {
+            let count = rng.below(12) as usize;
+            let lines = (0..count)
+                .map(|_| generate_line(&mut rng))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                count_overlaps(&lines),
+                VentMap::from_lines(&lines).count_overlaps(),
+                "{:?}",
+                lines
+            );
+        });
+    }
+
+    #[test]
+    fn test_count_overlaps_long_lines() {
+        let lines =
+            parse("0,0 -> 1000000000,0\n500000000,0 -> 2000000000,0\n7,-5 -> 7,5\n-3,-3 -> 3,3");
+
+        // the overlap of the horizontal lines, plus where the vertical line
+        // and the diagonal cross them; the vertical line and the diagonal
+        // would cross at (7, 7), past the end of both
+        assert_eq!(count_overlaps(&lines), 500000001 + 2);
+    }
+
+    #[test]
+    fn test_count_overlaps_near_limits() {
+        let max = i32::MAX;
+        let lines = vec![
+            Line {
+                start: (max - 10, max),
+                end: (max, max),
+            },
+            Line {
+                start: (max - 5, max),
+                end: (max, max),
+            },
+            Line {
+                start: (max - 20, max - 20),
+                end: (max, max),
+            },
+            Line {
+                start: (max - 3, max - 3),
+                end: (max - 3, max),
+            },
+        ];
+
+        // the horizontal lines overlap on 6 points, and the diagonal and the
+        // vertical line cross them at (max, max) and (max - 3, max) (both
+        // already in the overlap), and each other at (max - 3, max - 3)
+        assert_eq!(count_overlaps(&lines), 6 + 1);
+    }
+}
@@ -1,4 +1,10 @@
-use std::{collections::HashMap, iter};
+mod population;
+
+use std::collections::HashMap;
+
+use advent_of_code_2021_rust::bigint::BigUint;
+use population::Timers;
+
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
 fn p1(input: &str) -> String {
@@ -19,7 +25,8 @@ fn p1(input: &str) -> String {
                 *fish_days_left -= 1;
             }
         });
-        fishes_days_left.append(&mut iter::repeat(8).take(new_fishes_count).collect::<Vec<i32>>());
+        fishes_days_left
+            .append(&mut std::iter::repeat_n(8, new_fishes_count).collect::<Vec<i32>>());
     });
 
     fishes_days_left.len().to_string()
@@ -112,7 +119,45 @@ fn p2(input: &str) -> String {
     solve_efficient_bottomup(input, 256)
 }
 
+// the histogram and total after any number of days, exactly
+fn describe_population(input: &str, days: u64, timers: &Timers) -> String {
+    let histogram = population::get_histogram_after(
+        &population::parse_histogram::<BigUint>(input, timers),
+        days,
+        timers,
+    )
+    .unwrap();
+
+    histogram
+        .iter()
+        .enumerate()
+        .map(|(timer, count)| format!("{}: {}\n", timer, count))
+        .chain(std::iter::once(format!(
+            "total: {}",
+            population::get_total(&histogram).unwrap()
+        )))
+        .collect()
+}
+
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d06 -- days <days> [reset timer] [newborn timer]
+    if args.get(1).map(String::as_str) == Some("days") {
+        let days = args.get(2).expect("Missing days").parse().unwrap();
+        let default = Timers::default();
+        let timers = Timers {
+            reset: args
+                .get(3)
+                .map_or(default.reset, |arg| arg.parse().unwrap()),
+            newborn: args
+                .get(4)
+                .map_or(default.newborn, |arg| arg.parse().unwrap()),
+        };
+        println!("{}", describe_population(ACTUAL_INPUT, days, &timers));
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -123,6 +168,14 @@ mod tests {
 
     const SAMPLE_INPUT: &str = "3,4,3,1,2";
 
+    #[test]
+    fn test_describe_population() {
+        assert_eq!(
+            describe_population(SAMPLE_INPUT, 18, &Timers::default()),
+            "0: 3\n1: 5\n2: 3\n3: 2\n4: 2\n5: 1\n6: 5\n7: 1\n8: 4\ntotal: 26"
+        );
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(p1(SAMPLE_INPUT), "5934");
@@ -149,5 +202,7 @@ mod tests {
         assert_eq!(solve_efficient_bottomup(ACTUAL_INPUT, 80), p1(ACTUAL_INPUT));
 
         assert_eq!(p2(ACTUAL_INPUT), "1686252324092");
+        assert!(describe_population(ACTUAL_INPUT, 256, &Timers::default())
+            .ends_with("total: 1686252324092"));
     }
 }
@@ -0,0 +1,280 @@
+use advent_of_code_2021_rust::bigint::BigUint;
+
+// a fish count that can either overflow (and report it) or grow forever
+pub trait Count: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+}
+
+impl Count for u64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        u64::checked_mul(*self, *other)
+    }
+}
+
+impl Count for BigUint {
+    fn zero() -> Self {
+        BigUint::zero()
+    }
+
+    fn one() -> Self {
+        BigUint::from(1)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timers {
+    // the timer of a fish right after it gives birth
+    pub reset: usize,
+    // the timer of a newborn fish
+    pub newborn: usize,
+}
+
+impl Default for Timers {
+    fn default() -> Self {
+        Self {
+            reset: 6,
+            newborn: 8,
+        }
+    }
+}
+
+impl Timers {
+    // one bucket for every timer value, from 0 to the highest timer
+    pub fn total_buckets(&self) -> usize {
+        self.reset.max(self.newborn) + 1
+    }
+}
+
+type Matrix<T> = Vec<Vec<T>>;
+
+fn multiply<T: Count>(a: &Matrix<T>, b: &Matrix<T>) -> Option<Matrix<T>> {
+    (0..a.len())
+        .map(|i| {
+            (0..b[0].len())
+                .map(|j| {
+                    (0..b.len()).try_fold(T::zero(), |sum, k| {
+                        sum.checked_add(&a[i][k].checked_mul(&b[k][j])?)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// entry (to, from) is how many fishes with timer `to` a fish with timer
+// `from` becomes after a day
+fn get_transition<T: Count>(timers: &Timers) -> Matrix<T> {
+    let size = timers.total_buckets();
+    let mut transition = vec![vec![T::zero(); size]; size];
+
+    let mut increment = |to: usize, from: usize| {
+        transition[to][from] = transition[to][from].checked_add(&T::one()).unwrap();
+    };
+    (1..size).for_each(|from| increment(from - 1, from));
+    increment(timers.reset, 0);
+    increment(timers.newborn, 0);
+
+    transition
+}
+
+pub fn parse_histogram<T: Count>(input: &str, timers: &Timers) -> Vec<T> {
+    let mut histogram = vec![T::zero(); timers.total_buckets()];
+
+    input
+        .trim()
+        .split(',')
+        .map(str::parse::<usize>)
+        .map(Result::unwrap)
+        .for_each(|timer| {
+            if timer >= histogram.len() {
+                panic!(
+                    "Expected a timer of at most {}, found {}",
+                    histogram.len() - 1,
+                    timer
+                );
+            }
+            histogram[timer] = histogram[timer].checked_add(&T::one()).unwrap();
+        });
+
+    histogram
+}
+
+// how many fishes have each timer after `days`, or None if that doesn't fit
+// in T; the transition is raised to the power of `days` by squaring, so this
+// is logarithmic in the number of days
+pub fn get_histogram_after<T: Count>(
+    histogram: &[T],
+    days: u64,
+    timers: &Timers,
+) -> Option<Vec<T>> {
+    let mut power = get_transition::<T>(timers);
+    let mut result = histogram
+        .iter()
+        .map(|count| vec![count.clone()])
+        .collect::<Matrix<T>>();
+    let mut days = days;
+
+    while days > 0 {
+        if days & 1 == 1 {
+            result = multiply(&power, &result)?;
+        }
+        days >>= 1;
+        if days > 0 {
+            power = multiply(&power, &power)?;
+        }
+    }
+
+    Some(result.into_iter().map(|mut row| row.remove(0)).collect())
+}
+
+pub fn get_total<T: Count>(histogram: &[T]) -> Option<T> {
+    histogram
+        .iter()
+        .try_fold(T::zero(), |sum, count| sum.checked_add(count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = "3,4,3,1,2";
+
+    // one day at a time, as a reference
+    fn simulate(input: &str, days: usize, timers: &Timers) -> Vec<u64> {
+        (0..days).fold(parse_histogram(input, timers), |histogram, _| {
+            let mut next = histogram[1..].to_vec();
+            next.push(0);
+            next[timers.reset] += histogram[0];
+            next[timers.newborn] += histogram[0];
+            next
+        })
+    }
+
+    #[test]
+    fn test_histogram_after() {
+        let timers = Timers::default();
+        let histogram = parse_histogram::<u64>(SAMPLE_INPUT, &timers);
+
+        assert_eq!(
+            get_histogram_after(&histogram, 18, &timers),
+            Some(vec![3, 5, 3, 2, 2, 1, 5, 1, 4])
+        );
+        assert_eq!(
+            get_histogram_after(&histogram, 0, &timers),
+            Some(vec![0, 1, 1, 2, 1, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            get_total(&get_histogram_after(&histogram, 256, &timers).unwrap()),
+            Some(26984457539)
+        );
+    }
+
+    #[test]
+    fn test_custom_timers() {
+        [(6, 8), (2, 5), (4, 4), (7, 3), (0, 1)]
+            .into_iter()
+            .for_each(|(reset, newborn)| {
+                let timers = Timers { reset, newborn };
+                let input = "0,1,1";
+                (0..40).for_each(|days| {
+                    assert_eq!(
+                        get_histogram_after(&parse_histogram(input, &timers), days as u64, &timers),
+                        Some(simulate(input, days, &timers)),
+                        "{:?} {}",
+                        timers,
+                        days
+                    );
+                });
+            });
+    }
+
+    #[test]
+    fn test_overflow() {
+        let timers = Timers::default();
+
+        let checked = get_histogram_after(
+            &parse_histogram::<u64>(SAMPLE_INPUT, &timers),
+            1000,
+            &timers,
+        );
+        assert_eq!(checked, None);
+
+        let big = get_histogram_after(
+            &parse_histogram::<BigUint>(SAMPLE_INPUT, &timers),
+            500,
+            &timers,
+        )
+        .unwrap();
+        let total = get_total(&big).unwrap();
+        assert!(total.to_u64().is_none());
+
+        // the same count, one day at a time
+        let smaller = get_histogram_after(
+            &parse_histogram::<BigUint>(SAMPLE_INPUT, &timers),
+            499,
+            &timers,
+        )
+        .unwrap();
+        let next = get_histogram_after(&smaller, 1, &timers).unwrap();
+        assert_eq!(next, big);
+    }
+
+    #[test]
+    fn test_big_matches_checked() {
+        let timers = Timers {
+            reset: 4,
+            newborn: 6,
+        };
+
+        [0, 1, 18, 80, 256, 300].into_iter().for_each(|days| {
+            let checked = get_histogram_after(
+                &parse_histogram::<u64>(SAMPLE_INPUT, &timers),
+                days,
+                &timers,
+            )
+            .unwrap();
+            let big = get_histogram_after(
+                &parse_histogram::<BigUint>(SAMPLE_INPUT, &timers),
+                days,
+                &timers,
+            )
+            .unwrap();
+
+            assert_eq!(
+                big.iter().map(BigUint::to_u64).collect::<Vec<_>>(),
+                checked.into_iter().map(Some).collect::<Vec<_>>(),
+                "{} days",
+                days
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_timer_out_of_range() {
+        parse_histogram::<u64>("9", &Timers::default());
+    }
+}
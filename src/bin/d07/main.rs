@@ -1,53 +1,149 @@
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
-fn p1(input: &str) -> String {
-    let mut numbers = input
+fn parse_positions(input: &str) -> Vec<i64> {
+    let mut positions = input
         .trim()
         .split(',')
-        .map(str::parse::<i32>)
+        .map(str::parse::<i64>)
         .map(Result::unwrap)
         .collect::<Vec<_>>();
 
-    numbers.sort_unstable();
+    positions.sort_unstable();
+    positions
+}
 
-    let median = numbers[numbers.len() / 2];
+// the fuel a crab needs to move some number of steps; the cost must be convex
+// and never decrease as the steps increase, so the total fuel is convex in
+// the target position
+trait FuelCost {
+    fn cost(&self, steps: i64) -> i64;
 
-    numbers
-        .into_iter()
-        .map(|x| (median - x).abs())
-        .sum::<i32>()
-        .to_string()
+    // a (small) range of positions known to contain an optimum, given the
+    // sorted positions of the crabs
+    fn bounds(&self, _positions: &[i64]) -> Option<(i64, i64)> {
+        None
+    }
 }
 
-fn p2(input: &str) -> String {
-    let mut numbers = input
-        .trim()
-        .split(',')
-        .map(str::parse::<i32>)
-        .map(Result::unwrap)
-        .collect::<Vec<_>>();
+// p1: every step costs 1
+struct Linear;
 
-    numbers.sort_unstable();
+impl FuelCost for Linear {
+    fn cost(&self, steps: i64) -> i64 {
+        steps
+    }
+
+    // the median, where there are as many crabs on either side
+    fn bounds(&self, positions: &[i64]) -> Option<(i64, i64)> {
+        let median = positions[(positions.len() - 1) / 2];
+        Some((median, median))
+    }
+}
+
+// p2: every step costs 1 more than the previous one
+struct Triangular;
+
+impl FuelCost for Triangular {
+    fn cost(&self, steps: i64) -> i64 {
+        (steps * (steps + 1)) / 2
+    }
+
+    // the optimum is within 1/2 of the mean, since the derivative of the
+    // total is n * (target - mean) plus a term between -n/2 and n/2
+    fn bounds(&self, positions: &[i64]) -> Option<(i64, i64)> {
+        let n = positions.len() as i64;
+        let sum = positions.iter().sum::<i64>();
+        Some((sum.div_euclid(n) - 1, sum.div_euclid(n) + 1))
+    }
+}
+
+// any other convex cost
+impl<F: Fn(i64) -> i64> FuelCost for F {
+    fn cost(&self, steps: i64) -> i64 {
+        self(steps)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Alignment {
+    position: i64,
+    fuel: i64,
+}
+
+fn get_total_fuel(positions: &[i64], target: i64, fuel_cost: &dyn FuelCost) -> i64 {
+    positions
+        .iter()
+        .map(|position| fuel_cost.cost((target - position).abs()))
+        .sum()
+}
 
-    let min = numbers[0];
-    let max = numbers[numbers.len() - 1];
+// the first position where the total fuel stops decreasing, with a binary
+// search over the slope, which works since the total fuel is convex
+fn search(positions: &[i64], fuel_cost: &dyn FuelCost) -> i64 {
+    let (mut low, mut high) = (positions[0], positions[positions.len() - 1]);
 
-    (min..max)
-        .map(|point| {
-            numbers
-                .iter()
-                .map(|x| {
-                    let steps = (point - x).abs();
-                    (steps * (steps + 1)) / 2
-                })
-                .sum::<i32>()
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if get_total_fuel(positions, middle + 1, fuel_cost)
+            >= get_total_fuel(positions, middle, fuel_cost)
+        {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    low
+}
+
+// the cheapest position to align the (sorted) crabs on, and the leftmost one
+// when there are several
+fn optimise(positions: &[i64], fuel_cost: &dyn FuelCost) -> Alignment {
+    let (from, to) = fuel_cost.bounds(positions).unwrap_or_else(|| {
+        let position = search(positions, fuel_cost);
+        (position, position)
+    });
+
+    (from..=to)
+        .map(|position| Alignment {
+            position,
+            fuel: get_total_fuel(positions, position, fuel_cost),
         })
-        .min()
+        .min_by_key(|alignment| (alignment.fuel, alignment.position))
         .unwrap()
+}
+
+fn p1(input: &str) -> String {
+    optimise(&parse_positions(input), &Linear).fuel.to_string()
+}
+
+fn p2(input: &str) -> String {
+    optimise(&parse_positions(input), &Triangular)
+        .fuel
         .to_string()
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d07 -- align
+    if args.get(1).map(String::as_str) == Some("align") {
+        let positions = parse_positions(ACTUAL_INPUT);
+        [
+            ("Linear", &Linear as &dyn FuelCost),
+            ("Triangular", &Triangular),
+        ]
+        .into_iter()
+        .for_each(|(name, fuel_cost)| {
+            let alignment = optimise(&positions, fuel_cost);
+            println!(
+                "{}: position {}, fuel {}",
+                name, alignment.position, alignment.fuel
+            );
+        });
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -55,9 +151,66 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_2021_rust::rng::Rng;
 
     const SAMPLE_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
 
+    fn brute_force(positions: &[i64], fuel_cost: &dyn FuelCost) -> Alignment {
+        (positions[0]..=positions[positions.len() - 1])
+            .map(|position| Alignment {
+                position,
+                fuel: get_total_fuel(positions, position, fuel_cost),
+            })
+            .min_by_key(|alignment| (alignment.fuel, alignment.position))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_optimise_sample() {
+        let positions = parse_positions(SAMPLE_INPUT);
+
+        assert_eq!(
+            optimise(&positions, &Linear),
+            Alignment {
+                position: 2,
+                fuel: 37
+            }
+        );
+        assert_eq!(
+            optimise(&positions, &Triangular),
+            Alignment {
+                position: 5,
+                fuel: 168
+            }
+        );
+    }
+
+    #[test]
+    fn test_optimise_random() {
+        let mut rng = Rng::new(0x2021_0007);
+        let quadratic = |steps: i64| steps * steps;
+
+        (0..300).for_each(|_| {
+            let count = rng.below(12) as usize + 1;
+            let mut positions = (0..count)
+                .map(|_| rng.next_in_range(-10, 39))
+                .collect::<Vec<_>>();
+            positions.sort_unstable();
+
+            [&Linear as &dyn FuelCost, &Triangular, &quadratic]
+                .into_iter()
+                .for_each(|fuel_cost| {
+                    let expected = brute_force(&positions, fuel_cost);
+                    assert_eq!(optimise(&positions, fuel_cost), expected, "{:?}", positions);
+                    // the search alone finds the same fuel, even without bounds
+                    assert_eq!(
+                        get_total_fuel(&positions, search(&positions, fuel_cost), fuel_cost),
+                        expected.fuel
+                    );
+                });
+        });
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(p1(SAMPLE_INPUT), "37");
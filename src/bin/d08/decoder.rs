@@ -0,0 +1,477 @@
+use std::fmt;
+
+// the segments a display has, named by letters, and the segments each symbol
+// lights up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayDefinition {
+    segments: Vec<char>,
+    // (symbol, bit i set when segment i is lit)
+    symbols: Vec<(char, u32)>,
+}
+
+impl DisplayDefinition {
+    // a "segments <letters>" line, then a "<symbol> <lit segments>" line for
+    // every symbol; blank lines and lines starting with '#' are skipped
+    pub fn parse(text: &str) -> Self {
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let segments = lines
+            .next()
+            .and_then(|line| line.strip_prefix("segments "))
+            .expect("Expected the segments first")
+            .trim()
+            .chars()
+            .collect::<Vec<_>>();
+        if segments.len() > 32 {
+            panic!("Expected at most 32 segments, found {}", segments.len());
+        }
+
+        let mut definition = Self {
+            segments,
+            symbols: vec![],
+        };
+
+        lines.for_each(|line| {
+            let (symbol, lit) = line
+                .split_once(' ')
+                .unwrap_or_else(|| panic!("Expected a symbol and its segments, found '{}'", line));
+            let mut symbol = symbol.chars();
+            let (Some(symbol), None) = (symbol.next(), symbol.next()) else {
+                panic!("Expected a single character symbol, found '{}'", line);
+            };
+
+            let mask = definition
+                .parse_pattern(lit.trim())
+                .unwrap_or_else(|error| panic!("Invalid symbol {}: {}", symbol, error));
+            if definition.symbols.iter().any(|(_, other)| *other == mask) {
+                panic!("Symbol {} has the same segments as another symbol", symbol);
+            }
+            definition.symbols.push((symbol, mask));
+        });
+
+        definition
+    }
+
+    pub fn standard() -> Self {
+        Self::parse(include_str!("displays/standard.txt"))
+    }
+
+    // standard, hex or alphanumeric
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "standard" => Some(Self::standard()),
+            "hex" => Some(Self::parse(include_str!("displays/hex.txt"))),
+            "alphanumeric" => Some(Self::parse(include_str!("displays/alphanumeric.txt"))),
+            _ => None,
+        }
+    }
+
+    fn parse_pattern(&self, pattern: &str) -> Result<u32, DecodeError> {
+        pattern.chars().try_fold(0, |mask, c| {
+            match self.segments.iter().position(|segment| *segment == c) {
+                Some(i) => Ok(mask | (1 << i)),
+                None => Err(DecodeError::UnknownWire(c)),
+            }
+        })
+    }
+
+    fn get_symbol(&self, mask: u32) -> Option<char> {
+        self.symbols
+            .iter()
+            .find(|(_, lit)| *lit == mask)
+            .map(|(symbol, _)| *symbol)
+    }
+
+    // the symbols lighting up this many segments
+    pub fn symbols_with_length(&self, length: usize) -> usize {
+        self.symbols
+            .iter()
+            .filter(|(_, lit)| lit.count_ones() as usize == length)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    Malformed(String),
+    UnknownWire(char),
+    // no mapping turns every pattern into a symbol
+    Contradiction,
+    // more than one mapping does (only the first two found are kept)
+    Ambiguous(Vec<Mapping>),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Malformed(line) => write!(f, "malformed entry '{}'", line),
+            DecodeError::UnknownWire(wire) => write!(f, "unknown wire {}", wire),
+            DecodeError::Contradiction => write!(f, "no wire mapping fits every pattern"),
+            DecodeError::Ambiguous(mappings) => write!(
+                f,
+                "several wire mappings fit, e.g. {}",
+                mappings
+                    .iter()
+                    .map(Mapping::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            ),
+        }
+    }
+}
+
+// the segment every wire is connected to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    // (wire, segment) for every wire, in the definition's segment order
+    pub wires: Vec<(char, char)>,
+    segments: Vec<usize>,
+}
+
+impl Mapping {
+    fn new(definition: &DisplayDefinition, segments: Vec<usize>) -> Self {
+        Self {
+            wires: segments
+                .iter()
+                .enumerate()
+                .map(|(wire, segment)| (definition.segments[wire], definition.segments[*segment]))
+                .collect(),
+            segments,
+        }
+    }
+
+    fn apply(&self, pattern: u32) -> u32 {
+        self.segments
+            .iter()
+            .enumerate()
+            .filter(|(wire, _)| pattern & (1 << wire) != 0)
+            .fold(0, |mask, (_, segment)| mask | (1 << segment))
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.wires
+                .iter()
+                .map(|(wire, segment)| format!("{}->{}", wire, segment))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    pub mapping: Mapping,
+    pub outputs: Vec<char>,
+}
+
+impl Decoded {
+    pub fn get_value(&self) -> String {
+        self.outputs.iter().collect()
+    }
+}
+
+struct Solver<'a> {
+    definition: &'a DisplayDefinition,
+    patterns: Vec<u32>,
+    // stop searching once this many mappings have been found
+    limit: usize,
+    mappings: Vec<Vec<usize>>,
+}
+
+impl Solver<'_> {
+    // assigns a different symbol to every pattern, narrowing down the
+    // segments each wire can be connected to along the way
+    fn assign(&mut self, index: usize, possible: &[u32], used: &mut [bool]) {
+        if self.mappings.len() >= self.limit {
+            return;
+        }
+
+        if index == self.patterns.len() {
+            self.match_wires(0, possible, 0, &mut vec![]);
+            return;
+        }
+
+        let pattern = self.patterns[index];
+        (0..self.definition.symbols.len()).for_each(|symbol| {
+            let lit = self.definition.symbols[symbol].1;
+            if used[symbol] || lit.count_ones() != pattern.count_ones() {
+                return;
+            }
+
+            // the wires of the pattern go to the lit segments, and the others
+            // go anywhere else
+            let narrowed = possible
+                .iter()
+                .enumerate()
+                .map(|(wire, segments)| {
+                    if pattern & (1 << wire) != 0 {
+                        segments & lit
+                    } else {
+                        segments & !lit
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if narrowed.contains(&0) {
+                return;
+            }
+
+            used[symbol] = true;
+            self.assign(index + 1, &narrowed, used);
+            used[symbol] = false;
+        });
+    }
+
+    // every way to connect the wires to distinct segments
+    fn match_wires(
+        &mut self,
+        wire: usize,
+        possible: &[u32],
+        taken: u32,
+        segments: &mut Vec<usize>,
+    ) {
+        if self.mappings.len() >= self.limit {
+            return;
+        }
+
+        if wire == possible.len() {
+            self.mappings.push(segments.clone());
+            return;
+        }
+
+        (0..possible.len())
+            .filter(|segment| possible[wire] & !taken & (1 << segment) != 0)
+            .for_each(|segment| {
+                segments.push(segment);
+                self.match_wires(wire + 1, possible, taken | (1 << segment), segments);
+                segments.pop();
+            });
+    }
+}
+
+// recovers the wire mapping from every pattern on the line (the unique
+// patterns, then the outputs after the '|'), and decodes the outputs with it
+pub fn decode(line: &str, definition: &DisplayDefinition) -> Result<Decoded, DecodeError> {
+    let (patterns, outputs) = line
+        .split_once(" | ")
+        .ok_or_else(|| DecodeError::Malformed(line.to_string()))?;
+    let parse = |part: &str| {
+        part.split_whitespace()
+            .map(|pattern| definition.parse_pattern(pattern))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let outputs = parse(outputs)?;
+
+    let mut patterns = parse(patterns)?;
+    patterns.extend(outputs.iter().copied());
+    patterns.sort_unstable();
+    patterns.dedup();
+    // the patterns with the fewest candidate symbols narrow things down first
+    patterns.sort_by_key(|pattern| definition.symbols_with_length(pattern.count_ones() as usize));
+
+    let total_segments = definition.segments.len();
+    let mut solver = Solver {
+        definition,
+        patterns,
+        limit: 2,
+        mappings: vec![],
+    };
+    solver.assign(
+        0,
+        &vec![((1u64 << total_segments) - 1) as u32; total_segments],
+        &mut vec![false; definition.symbols.len()],
+    );
+
+    let mut mappings = solver
+        .mappings
+        .into_iter()
+        .map(|segments| Mapping::new(definition, segments));
+    match (mappings.next(), mappings.next()) {
+        (None, _) => Err(DecodeError::Contradiction),
+        (Some(first), Some(second)) => Err(DecodeError::Ambiguous(vec![first, second])),
+        (Some(mapping), None) => {
+            let outputs = outputs
+                .iter()
+                .map(|output| definition.get_symbol(mapping.apply(*output)).unwrap())
+                .collect();
+            Ok(Decoded { mapping, outputs })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_ENTRY: &str =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    #[test]
+    fn test_decode() {
+        let decoded = decode(SAMPLE_ENTRY, &DisplayDefinition::standard()).unwrap();
+
+        assert_eq!(decoded.get_value(), "5353");
+        assert_eq!(
+            decoded.mapping.to_string(),
+            "a->c b->f c->g d->a e->b f->d g->e"
+        );
+    }
+
+    // what the display would show for `symbols`, with the wires crossed by
+    // `wires`, a permutation of the segments
+    fn scramble(definition: &DisplayDefinition, wires: &str, symbols: &str) -> String {
+        let wires = wires.chars().collect::<Vec<_>>();
+        symbols
+            .chars()
+            .map(|symbol| {
+                let lit = definition
+                    .symbols
+                    .iter()
+                    .find(|(s, _)| *s == symbol)
+                    .unwrap()
+                    .1;
+                (0..definition.segments.len())
+                    .filter(|segment| lit & (1 << segment) != 0)
+                    .map(|segment| wires[segment])
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_parse() {
+        let definition = DisplayDefinition::parse("# a comment\n\nsegments xyz\n1 x\n2 yz\n");
+
+        assert_eq!(definition.segments, vec!['x', 'y', 'z']);
+        assert_eq!(definition.symbols, vec![('1', 0b001), ('2', 0b110)]);
+        assert_eq!(definition.symbols_with_length(2), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parse_duplicate() {
+        DisplayDefinition::parse("segments ab\n1 ab\n2 ba");
+    }
+
+    #[test]
+    fn test_builtin() {
+        [
+            ("standard", "abcdefg", "0123456789", "8675309"),
+            ("hex", "abcdefg", "0123456789AbCdEF", "dEAdbEEF"),
+            (
+                "alphanumeric",
+                "abcdefghijklmn",
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789",
+                "HELLO2021",
+            ),
+        ]
+        .into_iter()
+        .for_each(|(name, segments, symbols, output)| {
+            let definition = DisplayDefinition::builtin(name).unwrap();
+            assert_eq!(definition.segments.iter().collect::<String>(), segments);
+            assert_eq!(
+                definition
+                    .symbols
+                    .iter()
+                    .map(|(symbol, _)| symbol)
+                    .collect::<String>(),
+                symbols
+            );
+
+            // unscrambled, every wire goes to its own segment
+            let line = format!(
+                "{} | {}",
+                scramble(&definition, segments, symbols),
+                scramble(&definition, segments, output)
+            );
+            let decoded = decode(&line, &definition).unwrap();
+            assert_eq!(decoded.get_value(), output);
+            assert_eq!(
+                decoded.mapping.to_string(),
+                segments
+                    .chars()
+                    .map(|segment| format!("{}->{}", segment, segment))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+        });
+        assert_eq!(DisplayDefinition::builtin("unknown"), None);
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        let definition = DisplayDefinition::builtin("hex").unwrap();
+        let line = format!(
+            "{} | {}",
+            scramble(&definition, "gfedcba", "0123456789AbCdEF"),
+            scramble(&definition, "gfedcba", "C0FFEE")
+        );
+
+        let decoded = decode(&line, &definition).unwrap();
+        assert_eq!(decoded.get_value(), "C0FFEE");
+        assert_eq!(
+            decoded.mapping.to_string(),
+            "a->g b->f c->e d->d e->c f->b g->a"
+        );
+    }
+
+    #[test]
+    fn test_decode_alphanumeric() {
+        let definition = DisplayDefinition::builtin("alphanumeric").unwrap();
+        let wires = "nmlkjihgfedcba";
+        let line = format!(
+            "{} | {}",
+            scramble(&definition, wires, "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789"),
+            scramble(&definition, wires, "HELLO2021")
+        );
+
+        assert_eq!(decode(&line, &definition).unwrap().get_value(), "HELLO2021");
+    }
+
+    #[test]
+    fn test_decode_ambiguous() {
+        // 1 and 7 alone can't tell most wires apart
+        match decode("ab | dab", &DisplayDefinition::standard()) {
+            Err(DecodeError::Ambiguous(mappings)) => assert_eq!(mappings.len(), 2),
+            result => panic!("Expected an ambiguous entry, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_decode_contradiction() {
+        // two different patterns can't both be 1
+        assert_eq!(
+            decode("ab | cd", &DisplayDefinition::standard()),
+            Err(DecodeError::Contradiction)
+        );
+        // no symbol lights up 5 segments out of these
+        assert_eq!(
+            decode(
+                "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | abcde",
+                &DisplayDefinition::standard()
+            ),
+            Err(DecodeError::Contradiction)
+        );
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        assert_eq!(
+            decode("ab", &DisplayDefinition::standard()),
+            Err(DecodeError::Malformed("ab".to_string()))
+        );
+        assert_eq!(
+            decode("ab | xy", &DisplayDefinition::standard()),
+            Err(DecodeError::UnknownWire('x'))
+        );
+    }
+}
@@ -1,8 +1,13 @@
-use std::collections::HashSet;
+mod decoder;
+
+use decoder::DisplayDefinition;
 
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
+// the outputs that can only be one symbol, just by how many segments are lit
 fn p1(input: &str) -> String {
+    let definition = DisplayDefinition::standard();
+
     input
         .trim()
         .lines()
@@ -11,7 +16,7 @@ fn p1(input: &str) -> String {
                 .nth(1)
                 .unwrap()
                 .split(' ')
-                .filter(|pattern| matches!(pattern.len(), 2 | 3 | 4 | 7))
+                .filter(|pattern| definition.symbols_with_length(pattern.len()) == 1)
                 .count() as i32
         })
         .sum::<i32>()
@@ -19,77 +24,40 @@ fn p1(input: &str) -> String {
 }
 
 fn p2(input: &str) -> String {
-    // logic adapted from https://github.com/Fadi88/AoC/blob/master/2021/day08/main.rs
+    let definition = DisplayDefinition::standard();
+
     input
         .trim()
         .lines()
-        .map(|line| {
-            let mut parts = line.split(" | ").map(|part| {
-                part.split(' ')
-                    .map(|pattern| HashSet::from_iter(pattern.chars()))
-                    .collect::<Vec<_>>()
-            });
-
-            let unique_patterns = parts.next().unwrap();
-            let outputs = parts.next().unwrap();
-            if parts.next().is_some() {
-                panic!("Residue parts found after splitting by |");
-            }
-
-            let mut mappings: [HashSet<char>; 10] = Default::default();
-
-            let mut remaining_5 = vec![];
-            let mut remaining_6 = vec![];
-
-            unique_patterns
-                .into_iter()
-                .for_each(|pattern| match pattern.len() {
-                    2 => mappings[1] = pattern,
-                    3 => mappings[7] = pattern,
-                    4 => mappings[4] = pattern,
-                    7 => mappings[8] = pattern,
-                    5 => remaining_5.push(pattern),
-                    6 => remaining_6.push(pattern),
-                    _ => panic!("Unusual length"),
-                });
-
-            remaining_5.into_iter().for_each(|pattern| {
-                if mappings[1].difference(&pattern).count() == 0 {
-                    mappings[3] = pattern;
-                } else if pattern.difference(&mappings[4]).count() == 2 {
-                    mappings[5] = pattern;
-                } else {
-                    mappings[2] = pattern;
-                }
-            });
-
-            remaining_6.into_iter().for_each(|pattern| {
-                if mappings[1].difference(&pattern).count() == 1 {
-                    mappings[6] = pattern;
-                } else if mappings[5].difference(&pattern).count() == 0 {
-                    mappings[9] = pattern;
-                } else {
-                    mappings[0] = pattern;
-                }
-            });
-
-            outputs
-                .into_iter()
-                .map(|x| {
-                    for (i, item) in mappings.iter().enumerate() {
-                        if *item == x {
-                            return i as i32;
-                        }
-                    }
-                    panic!("Cannot find correct mapping for {:?}", x);
-                })
-                .fold(0, |acc, digit| acc * 10 + digit)
+        .map(|line| match decoder::decode(line, &definition) {
+            Ok(decoded) => decoded.get_value().parse::<i32>().unwrap(),
+            Err(error) => panic!("Cannot decode '{}': {}", line, error),
         })
         .sum::<i32>()
         .to_string()
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d08 -- decode [standard|hex|alphanumeric|<definition file>]
+    if args.get(1).map(String::as_str) == Some("decode") {
+        let definition = match args.get(2) {
+            None => DisplayDefinition::standard(),
+            Some(name) => DisplayDefinition::builtin(name).unwrap_or_else(|| {
+                DisplayDefinition::parse(&std::fs::read_to_string(name).unwrap())
+            }),
+        };
+        ACTUAL_INPUT
+            .trim()
+            .lines()
+            .for_each(|line| match decoder::decode(line, &definition) {
+                Ok(decoded) => println!("{} ({})", decoded.get_value(), decoded.mapping),
+                Err(error) => println!("error: {}", error),
+            });
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
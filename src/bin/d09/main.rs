@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, iter};
+use std::collections::VecDeque;
 
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
@@ -40,13 +40,13 @@ impl Grid {
 
         (0..self.grid.len()).for_each(|r| {
             (0..self.grid[0].len()).for_each(|c| {
-                let adjacents_higher = self.get_neighbours((r, c)).into_iter().all(|coord| {
-                    self.grid[coord.0 as usize][coord.1 as usize]
-                        > self.grid[r as usize][c as usize]
-                });
+                let adjacents_higher = self
+                    .get_neighbours((r, c))
+                    .into_iter()
+                    .all(|coord| self.grid[coord.0][coord.1] > self.grid[r][c]);
 
                 if adjacents_higher {
-                    result.push((r as usize, c as usize));
+                    result.push((r, c));
                 }
             });
         });
@@ -78,66 +78,125 @@ fn p1(input: &str) -> String {
         .to_string()
 }
 
-fn p2(input: &str) -> String {
-    let grid = Grid::from_input(input);
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Basin {
+    low_point: (usize, usize),
+    // in the order they were reached, starting from the low point
+    cells: Vec<(usize, usize)>,
+}
+
+impl Basin {
+    fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
 
-    let unassigned = 99999;
+#[derive(Debug)]
+struct BasinMap {
+    basins: Vec<Basin>,
+    // the basin of every cell, if any
+    labels: Vec<Vec<Option<usize>>>,
+    // cells at least this high aren't part of any basin
+    wall: i32,
+}
 
-    let mut basin_assignment = iter::repeat(
-        iter::repeat(unassigned)
-            .take(grid.width())
-            .collect::<Vec<_>>(),
-    )
-    .take(grid.height())
-    .collect::<Vec<_>>();
-    let low_point_coords = grid.get_low_point_coords();
-    let total_low_points = low_point_coords.len();
+impl BasinMap {
+    // a single search from every low point at once, where every cell is
+    // labelled the first time it's reached, and never visited again
+    fn new(grid: &Grid, wall: i32) -> Self {
+        let mut labels = vec![vec![None; grid.width()]; grid.height()];
+        let mut basins = vec![];
+        let mut to_visit = VecDeque::new();
 
-    low_point_coords
-        .into_iter()
-        .enumerate()
-        .for_each(|(index, coord)| {
-            let mut to_visit = VecDeque::new();
-
-            basin_assignment[coord.0][coord.1] = index;
-            to_visit.push_back(coord);
-
-            loop {
-                match to_visit.pop_front() {
-                    None => break,
-                    Some(coord) => {
-                        #[allow(clippy::needless_collect)]
-                        // reason = "Collect is actually needed to avoid borrow checker errors"
-                        let unvisited_valid_neighbours = grid
-                            .get_neighbours(coord)
-                            .into_iter()
-                            .filter(|neighbour_coord| {
-                                basin_assignment[neighbour_coord.0][neighbour_coord.1] == unassigned
-                            })
-                            .filter(|neighbour_coord| {
-                                grid.get(neighbour_coord) != 9
-                                    && grid.get(neighbour_coord) > grid.get(&coord)
-                            })
-                            .collect::<Vec<_>>();
-
-                        unvisited_valid_neighbours
-                            .into_iter()
-                            .for_each(|neighbour_coord| {
-                                basin_assignment[neighbour_coord.0][neighbour_coord.1] = index;
-                                to_visit.push_back(neighbour_coord)
-                            });
+        grid.get_low_point_coords()
+            .into_iter()
+            .filter(|coord| grid.get(coord) < wall)
+            .for_each(|coord| {
+                labels[coord.0][coord.1] = Some(basins.len());
+                to_visit.push_back((coord, basins.len()));
+                basins.push(Basin {
+                    low_point: coord,
+                    cells: vec![coord],
+                });
+            });
+
+        while let Some((coord, index)) = to_visit.pop_front() {
+            grid.get_neighbours(coord)
+                .into_iter()
+                .filter(|neighbour| {
+                    grid.get(neighbour) < wall && grid.get(neighbour) > grid.get(&coord)
+                })
+                .for_each(|neighbour| {
+                    if labels[neighbour.0][neighbour.1].is_none() {
+                        labels[neighbour.0][neighbour.1] = Some(index);
+                        basins[index].cells.push(neighbour);
+                        to_visit.push_back((neighbour, index));
                     }
-                }
+                });
+        }
+
+        Self {
+            basins,
+            labels,
+            wall,
+        }
+    }
+
+    // a letter for every basin (cycling through the alphabet), in upper case
+    // on its low point; '#' for walls, and '.' for cells in no basin
+    fn render_letters(&self, grid: &Grid) -> String {
+        self.render(grid, |_, index, is_low_point| {
+            let letter = (b'a' + (index % 26) as u8) as char;
+            if is_low_point {
+                letter.to_ascii_uppercase().to_string()
+            } else {
+                letter.to_string()
             }
-        });
+        })
+    }
 
-    let mut basin_sizes = (0..total_low_points)
-        .map(|index| {
-            basin_assignment
-                .iter()
-                .map(|row| row.iter().filter(|&&cell| cell == index).count())
-                .sum::<usize>()
+    // the height of every basin cell, on a background colour for its basin
+    // (in bold on its low point)
+    fn render_ansi(&self, grid: &Grid) -> String {
+        self.render(grid, |coord, index, is_low_point| {
+            format!(
+                "\x1b[{};{}m{}\x1b[0m",
+                if is_low_point { 1 } else { 22 },
+                41 + index % 6,
+                grid.get(&coord)
+            )
         })
+    }
+
+    fn render(
+        &self,
+        grid: &Grid,
+        basin_cell: impl Fn((usize, usize), usize, bool) -> String,
+    ) -> String {
+        (0..grid.height())
+            .map(|r| {
+                (0..grid.width())
+                    .map(|c| match self.labels[r][c] {
+                        Some(index) => {
+                            basin_cell((r, c), index, self.basins[index].low_point == (r, c))
+                        }
+                        None if grid.get(&(r, c)) >= self.wall => "#".to_string(),
+                        None => ".".to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn p2(input: &str) -> String {
+    let grid = Grid::from_input(input);
+
+    let mut basin_sizes = BasinMap::new(&grid, 9)
+        .basins
+        .iter()
+        .map(Basin::size)
         .collect::<Vec<_>>();
 
     basin_sizes.sort_unstable();
@@ -151,6 +210,25 @@ fn p2(input: &str) -> String {
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d09 -- basins [--wall <height>] [--colour]
+    if args.get(1).map(String::as_str) == Some("basins") {
+        let wall = args.iter().position(|arg| arg == "--wall").map_or(9, |i| {
+            args.get(i + 1).expect("Missing wall").parse().unwrap()
+        });
+        let grid = Grid::from_input(ACTUAL_INPUT);
+        let basin_map = BasinMap::new(&grid, wall);
+
+        if args.iter().any(|arg| arg == "--colour") {
+            println!("{}", basin_map.render_ansi(&grid));
+        } else {
+            println!("{}", basin_map.render_letters(&grid));
+        }
+        println!("{} basins", basin_map.basins.len());
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -167,6 +245,59 @@ mod tests {
 9899965678
 ";
 
+    #[test]
+    fn test_basins() {
+        let grid = Grid::from_input(SAMPLE_INPUT);
+        let basin_map = BasinMap::new(&grid, 9);
+
+        assert_eq!(
+            basin_map
+                .basins
+                .iter()
+                .map(|basin| (basin.low_point, basin.size()))
+                .collect::<Vec<_>>(),
+            vec![((0, 1), 3), ((0, 9), 9), ((2, 2), 14), ((4, 6), 9)]
+        );
+        assert_eq!(basin_map.basins[0].cells, vec![(0, 1), (0, 0), (1, 0)]);
+        assert_eq!(
+            basin_map.render_letters(&grid),
+            r"aA###bbbbB
+a#ccc#b#bb
+#cCccc#d#b
+ccccc#ddd#
+#c###dDddd"
+        );
+    }
+
+    #[test]
+    fn test_basins_wall() {
+        let grid = Grid::from_input(SAMPLE_INPUT);
+        let basin_map = BasinMap::new(&grid, 6);
+
+        assert_eq!(
+            basin_map.render_letters(&grid),
+            r"aA###bbbbB
+a#####b#bb
+##C######b
+##########
+######D###"
+        );
+        // the two basins in the middle are walled in to their low points
+        assert_eq!(
+            basin_map.basins.iter().map(Basin::size).collect::<Vec<_>>(),
+            vec![3, 9, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let grid = Grid::from_input("19\n99");
+        assert_eq!(
+            BasinMap::new(&grid, 9).render_ansi(&grid),
+            "\x1b[1;41m1\x1b[0m#\n##"
+        );
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(p1(SAMPLE_INPUT), "15");
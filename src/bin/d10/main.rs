@@ -1,75 +1,182 @@
+use std::fmt;
+
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
-enum Score {
-    Corrupted(u64),
-    Incomplete(u64),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pair {
+    open: char,
+    close: char,
+    // the score of a corrupted line, when this is the illegal closer
+    corrupted_score: u64,
+    // the score of this closer in a completion string
+    completion_score: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Diagnostic {
+    Valid,
+    // `column` counts from 1, and nothing is expected when a closer comes
+    // before anything was opened
+    Corrupted {
+        column: usize,
+        expected: Option<char>,
+        found: char,
+    },
+    // the closers that complete the line, in order
+    Incomplete {
+        completion: String,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::Valid => write!(f, "valid"),
+            Diagnostic::Corrupted {
+                column,
+                expected: Some(expected),
+                found,
+            } => write!(
+                f,
+                "corrupted at column {}: expected {}, found {}",
+                column, expected, found
+            ),
+            Diagnostic::Corrupted {
+                column,
+                expected: None,
+                found,
+            } => write!(
+                f,
+                "corrupted at column {}: found {} with nothing to close",
+                column, found
+            ),
+            Diagnostic::Incomplete { completion } => {
+                write!(f, "incomplete: complete with {}", completion)
+            }
+        }
+    }
+}
+
+struct SyntaxChecker {
+    pairs: Vec<Pair>,
 }
 
-impl Score {
-    pub fn calculate(line: &str) -> Self {
+impl SyntaxChecker {
+    fn new(pairs: Vec<Pair>) -> Self {
+        Self { pairs }
+    }
+
+    fn standard() -> Self {
+        let pair = |open, close, corrupted_score, completion_score| Pair {
+            open,
+            close,
+            corrupted_score,
+            completion_score,
+        };
+
+        Self::new(vec![
+            pair('(', ')', 3, 1),
+            pair('[', ']', 57, 2),
+            pair('{', '}', 1197, 3),
+            pair('<', '>', 25137, 4),
+        ])
+    }
+
+    fn find_pair(&self, c: char) -> Option<&Pair> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.open == c || pair.close == c)
+    }
+
+    // characters that aren't in any pair are reported as corrupted
+    fn check(&self, line: &str) -> Diagnostic {
         let mut stack = vec![];
 
-        for c in line.chars() {
-            match c {
-                '(' => stack.push(')'),
-                '[' => stack.push(']'),
-                '{' => stack.push('}'),
-                '<' => stack.push('>'),
+        for (i, c) in line.chars().enumerate() {
+            match self.find_pair(c) {
+                Some(pair) if pair.open == c => stack.push(pair.close),
                 _ => {
-                    let expected_closing = stack.pop().unwrap();
-                    if c != expected_closing {
-                        return Score::Corrupted(match c {
-                            ')' => 3,
-                            ']' => 57,
-                            '}' => 1197,
-                            '>' => 25137,
-                            _ => unreachable!(),
-                        });
+                    let expected = stack.pop();
+                    if expected != Some(c) {
+                        return Diagnostic::Corrupted {
+                            column: i + 1,
+                            expected,
+                            found: c,
+                        };
                     }
                 }
             }
         }
 
-        Score::Incomplete(stack.into_iter().rev().fold(0u64, |acc, c| {
-            acc * 5
-                + match c {
-                    ')' => 1,
-                    ']' => 2,
-                    '}' => 3,
-                    '>' => 4,
-                    _ => unreachable!(),
-                }
-        }))
+        if stack.is_empty() {
+            Diagnostic::Valid
+        } else {
+            Diagnostic::Incomplete {
+                completion: stack.into_iter().rev().collect(),
+            }
+        }
+    }
+
+    // the closers that complete an incomplete line
+    fn complete(&self, line: &str) -> Option<String> {
+        match self.check(line) {
+            Diagnostic::Incomplete { completion } => Some(completion),
+            _ => None,
+        }
+    }
+
+    // an incomplete line is completed; a corrupted line is kept as it is,
+    // with a caret under the illegal character on the line below
+    fn repair(&self, line: &str) -> String {
+        match self.check(line) {
+            Diagnostic::Valid => line.to_string(),
+            Diagnostic::Incomplete { completion } => format!("{}{}", line, completion),
+            diagnostic @ Diagnostic::Corrupted { column, .. } => {
+                format!("{}\n{}^ {}", line, " ".repeat(column - 1), diagnostic)
+            }
+        }
+    }
+
+    fn get_corrupted_score(&self, found: char) -> u64 {
+        self.find_pair(found)
+            .filter(|pair| pair.close == found)
+            .map_or(0, |pair| pair.corrupted_score)
+    }
+
+    // None if the score doesn't fit in a u64, which happens around 28 closers
+    fn get_completion_score(&self, completion: &str) -> Option<u64> {
+        completion.chars().try_fold(0u64, |acc, c| {
+            acc.checked_mul(5)?
+                .checked_add(self.find_pair(c).map_or(0, |pair| pair.completion_score))
+        })
     }
 }
 
 fn p1(input: &str) -> String {
+    let checker = SyntaxChecker::standard();
+
     input
         .trim()
         .lines()
-        .map(Score::calculate)
-        .map(|s| {
-            if let Score::Corrupted(value) = s {
-                value
-            } else {
-                0
-            }
+        .map(|line| match checker.check(line) {
+            Diagnostic::Corrupted { found, .. } => checker.get_corrupted_score(found),
+            _ => 0,
         })
         .sum::<u64>()
         .to_string()
 }
 
 fn p2(input: &str) -> String {
+    let checker = SyntaxChecker::standard();
+
     let mut scores = input
         .trim()
         .lines()
-        .map(Score::calculate)
-        .filter_map(|s| {
-            if let Score::Incomplete(value) = s {
-                Some(value)
-            } else {
-                None
-            }
+        .filter_map(|line| checker.complete(line))
+        .map(|completion| {
+            checker
+                .get_completion_score(&completion)
+                .unwrap_or_else(|| panic!("Completion score of '{}' overflows u64", completion))
         })
         .collect::<Vec<_>>();
 
@@ -79,6 +186,31 @@ fn p2(input: &str) -> String {
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    match args.get(1).map(String::as_str) {
+        // cargo run --bin d10 -- check
+        Some("check") => {
+            let checker = SyntaxChecker::standard();
+            ACTUAL_INPUT
+                .trim()
+                .lines()
+                .enumerate()
+                .for_each(|(i, line)| println!("line {}: {}", i + 1, checker.check(line)));
+            return;
+        }
+        // cargo run --bin d10 -- repair
+        Some("repair") => {
+            let checker = SyntaxChecker::standard();
+            ACTUAL_INPUT
+                .trim()
+                .lines()
+                .for_each(|line| println!("{}", checker.repair(line)));
+            return;
+        }
+        _ => {}
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -100,6 +232,145 @@ mod tests {
 <{([{{}}[<[[[<>{}]]]>[]]
 ";
 
+    #[test]
+    fn test_check() {
+        let checker = SyntaxChecker::standard();
+
+        assert_eq!(
+            checker.check("{([(<{}[<>[]}>{[]{[(<()>"),
+            Diagnostic::Corrupted {
+                column: 13,
+                expected: Some(']'),
+                found: '}'
+            }
+        );
+        assert_eq!(
+            checker.check("[({(<(())[]>[[{[]{<()<>>"),
+            Diagnostic::Incomplete {
+                completion: "}}]])})]".to_string()
+            }
+        );
+        assert_eq!(checker.check("<([]){()}[{}]>"), Diagnostic::Valid);
+    }
+
+    #[test]
+    fn test_check_unmatched() {
+        let checker = SyntaxChecker::standard();
+
+        assert_eq!(
+            checker.check(")("),
+            Diagnostic::Corrupted {
+                column: 1,
+                expected: None,
+                found: ')'
+            }
+        );
+        assert_eq!(
+            checker.check("(x)"),
+            Diagnostic::Corrupted {
+                column: 2,
+                expected: Some(')'),
+                found: 'x'
+            }
+        );
+        assert_eq!(checker.get_corrupted_score('x'), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let checker = SyntaxChecker::standard();
+
+        assert_eq!(
+            checker.check("[[<[([]))<([[{}[[()]]]").to_string(),
+            "corrupted at column 9: expected ], found )"
+        );
+        assert_eq!(
+            checker.check("]").to_string(),
+            "corrupted at column 1: found ] with nothing to close"
+        );
+        assert_eq!(
+            checker.check("[(()[<>])]({[<{<<[]>>(").to_string(),
+            "incomplete: complete with )}>]})"
+        );
+    }
+
+    #[test]
+    fn test_complete() {
+        let checker = SyntaxChecker::standard();
+
+        assert_eq!(
+            SAMPLE_INPUT
+                .trim()
+                .lines()
+                .filter_map(|line| checker.complete(line))
+                .collect::<Vec<_>>(),
+            vec!["}}]])})]", ")}>]})", "}}>}>))))", "]]}}]}]}>", "])}>"]
+        );
+    }
+
+    #[test]
+    fn test_completion_score_overflow() {
+        let checker = SyntaxChecker::standard();
+
+        // n closers of 4 points score 5^n - 1
+        assert_eq!(
+            checker.get_completion_score(&">".repeat(27)),
+            Some(5u64.pow(27) - 1)
+        );
+        assert_eq!(checker.get_completion_score(&">".repeat(28)), None);
+    }
+
+    #[test]
+    fn test_repair() {
+        let checker = SyntaxChecker::standard();
+
+        assert_eq!(
+            checker.repair("<{([{{}}[<[[[<>{}]]]>[]]"),
+            "<{([{{}}[<[[[<>{}]]]>[]]])}>"
+        );
+        assert_eq!(
+            checker.repair("[<(<(<(<{}))><([]([]()"),
+            "[<(<(<(<{}))><([]([]()\n          ^ corrupted at column 11: expected >, found )"
+        );
+        assert_eq!(checker.repair("()"), "()");
+    }
+
+    #[test]
+    fn test_custom_pairs() {
+        let checker = SyntaxChecker::new(vec![Pair {
+            open: '«',
+            close: '»',
+            corrupted_score: 7,
+            completion_score: 2,
+        }]);
+
+        assert_eq!(
+            checker.check("««»"),
+            Diagnostic::Incomplete {
+                completion: "»".to_string()
+            }
+        );
+        assert_eq!(checker.get_completion_score("»»"), Some(12));
+        assert_eq!(
+            checker.check("»"),
+            Diagnostic::Corrupted {
+                column: 1,
+                expected: None,
+                found: '»'
+            }
+        );
+        assert_eq!(checker.get_corrupted_score('»'), 7);
+        // the standard brackets mean nothing here
+        assert_eq!(
+            checker.check("«()»"),
+            Diagnostic::Corrupted {
+                column: 2,
+                expected: Some('»'),
+                found: '('
+            }
+        );
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(p1(SAMPLE_INPUT), "26397");
@@ -1,87 +1,159 @@
-use std::collections::VecDeque;
+use std::{collections::HashMap, io};
+
+use advent_of_code_2021_rust::{
+    automaton::{parse_rows, Automaton, Cycle, Grid, Neighbourhood},
+    replay::{self, ReplayOptions},
+};
 
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Octopus {
+    Energy(i32),
+    // flashes on the current round of the cascade, which is the given wave
+    // (0 for the octopuses that flash without help from a neighbour)
+    Flashing(usize),
+    // has already flashed during this step, in the given wave (energy is
+    // reset to 0)
+    Flashed(usize),
+}
+
+fn increase_energy(energy: i32, amount: i32, threshold: i32, wave: usize) -> Octopus {
+    if energy + amount > threshold {
+        Octopus::Flashing(wave)
+    } else {
+        Octopus::Energy(energy + amount)
+    }
+}
+
+fn get_energy(octopus: Octopus) -> i32 {
+    match octopus {
+        Octopus::Energy(energy) => energy,
+        Octopus::Flashing(_) | Octopus::Flashed(_) => 0,
+    }
+}
+
+// which octopuses flashed during a step, as (row, column)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StepRecord {
+    // the octopuses of every wave of the cascade, in order
+    waves: Vec<Vec<(usize, usize)>>,
+}
+
+impl StepRecord {
+    fn count_flashed(&self) -> usize {
+        self.waves.iter().map(Vec::len).sum()
+    }
+}
+
 struct Cavern {
-    grid: Vec<Vec<i32>>,
+    automaton: Automaton<'static, Octopus>,
 }
 
 impl Cavern {
     fn from_input(input: &str) -> Self {
+        Self::with_threshold(input, 9)
+    }
+
+    // an octopus flashes once its energy goes over the threshold
+    fn with_threshold(input: &str, threshold: i32) -> Self {
+        let grid = Grid::bounded(parse_rows(input, |c| {
+            Octopus::Energy(c as i32 - '0' as i32)
+        }));
+
         Self {
-            grid: input
-                .trim()
-                .lines()
-                .map(|line| {
-                    line.chars()
-                        .map(|c| c as i32 - '0' as i32)
-                        .collect::<Vec<_>>()
+            automaton: Automaton::new(grid)
+                // every octopus gains one energy
+                .with_phase(Neighbourhood::new(vec![]), move |octopus, _| {
+                    increase_energy(get_energy(octopus), 1, threshold, 0)
                 })
-                .collect::<Vec<_>>(),
+                // flashes spread to the neighbours, until nobody flashes anymore
+                .with_phase_until_stable(Neighbourhood::moore(), move |octopus, neighbours| {
+                    match octopus {
+                        Octopus::Energy(energy) => {
+                            let flashing = neighbours
+                                .iter()
+                                .filter_map(|n| match n {
+                                    Octopus::Flashing(wave) => Some(*wave),
+                                    _ => None,
+                                })
+                                .collect::<Vec<_>>();
+                            // every neighbour flashing now is in the same wave
+                            let wave = flashing.first().map_or(0, |wave| wave + 1);
+                            increase_energy(energy, flashing.len() as i32, threshold, wave)
+                        }
+                        Octopus::Flashing(wave) | Octopus::Flashed(wave) => Octopus::Flashed(wave),
+                    }
+                }),
         }
     }
 
     fn get_height(&self) -> usize {
-        self.grid.len()
+        self.automaton.grid().height()
     }
 
     fn get_width(&self) -> usize {
-        self.grid[0].len()
+        self.automaton.grid().width()
     }
 
+    // (row, column) coordinates, only needed to check the neighbourhood
+    #[cfg(test)]
     fn get_neighbour_coords(&self, coord: (usize, usize)) -> Vec<(usize, usize)> {
-        let coord = (coord.0 as i64, coord.1 as i64);
-        let height = self.get_height() as i64;
-        let width = self.get_width() as i64;
-
-        ((coord.0 - 1)..=(coord.0 + 1))
-            .flat_map(|r| {
-                ((coord.1 - 1)..=(coord.1 + 1))
-                    .map(|c| (r, c))
-                    .collect::<Vec<_>>()
-            })
-            .filter(|(r, c)| {
-                !(*r == coord.0 && *c == coord.1) && *r >= 0 && *r < height && *c >= 0 && *c < width
-            })
-            .map(|(r, c)| (r as usize, c as usize))
-            .collect::<Vec<_>>()
+        self.automaton
+            .grid()
+            .neighbour_coords(coord.1 as i64, coord.0 as i64, &Neighbourhood::moore())
+            .into_iter()
+            .map(|(x, y)| (y as usize, x as usize))
+            .collect()
     }
 
     fn step(&mut self) -> i32 {
-        let mut counter = 0;
-        let mut to_visit_for_flash = VecDeque::new();
+        self.step_with_record().count_flashed() as i32
+    }
 
-        self.grid.iter_mut().enumerate().for_each(|(r, row)| {
-            row.iter_mut().enumerate().for_each(|(c, cell)| {
-                *cell += 1;
+    fn step_with_record(&mut self) -> StepRecord {
+        self.automaton.step();
 
-                if *cell > 9 {
-                    to_visit_for_flash.push_back((r, c));
+        let mut waves = vec![];
+        self.automaton.grid().iter().for_each(|((x, y), octopus)| {
+            if let Octopus::Flashed(wave) = octopus {
+                if waves.len() <= wave {
+                    waves.resize(wave + 1, vec![]);
                 }
-            });
-        });
-
-        while let Some(coord) = to_visit_for_flash.pop_front() {
-            if self.grid[coord.0][coord.1] == 0 {
-                // already flashed once
-                continue;
+                waves[wave].push((y as usize, x as usize));
             }
+        });
 
-            counter += 1;
-            self.grid[coord.0][coord.1] = 0;
-
-            self.get_neighbour_coords(coord).into_iter().for_each(|c| {
-                if self.grid[c.0][c.1] != 0 {
-                    self.grid[c.0][c.1] += 1;
+        StepRecord { waves }
+    }
 
-                    if self.grid[c.0][c.1] > 9 {
-                        to_visit_for_flash.push_back(c);
-                    }
-                }
-            });
-        }
+    fn record_history(&mut self, steps: usize) -> Vec<StepRecord> {
+        (0..steps).map(|_| self.step_with_record()).collect()
+    }
 
-        counter
+    // the steps start counting from 0 (before any step); from `start` on,
+    // the energies (and so the flashes) repeat every `period` steps, i.e.
+    // with a phase of start % period
+    fn find_periodicity(&mut self, max_steps: usize) -> Option<Cycle> {
+        let energies = |cavern: &Cavern| {
+            cavern
+                .automaton
+                .grid()
+                .iter()
+                .map(|(_, octopus)| get_energy(octopus))
+                .collect::<Vec<_>>()
+        };
+
+        let mut seen = HashMap::new();
+        seen.insert(energies(self), 0);
+
+        (1..=max_steps).find_map(|step| {
+            self.step();
+            seen.insert(energies(self), step).map(|start| Cycle {
+                start,
+                period: step - start,
+            })
+        })
     }
 }
 
@@ -107,7 +179,71 @@ fn p2(input: &str) -> String {
     step.to_string()
 }
 
+// stops once every octopus flashes at the same time
+fn replay_cavern(input: &str, options: &ReplayOptions) {
+    let mut cavern = Cavern::from_input(input);
+
+    replay::replay(
+        &mut cavern.automaton,
+        options,
+        |octopus| match octopus {
+            Octopus::Energy(energy) => char::from_digit(energy as u32, 10).unwrap(),
+            Octopus::Flashing(_) | Octopus::Flashed(_) => '*',
+        },
+        |octopus| match octopus {
+            Octopus::Energy(energy) => [0, (energy * 16) as u8, (energy * 24) as u8],
+            Octopus::Flashing(_) | Octopus::Flashed(_) => [255, 255, 255],
+        },
+        |grid| grid.count(|octopus| !matches!(octopus, Octopus::Flashed(_))) == 0,
+        &mut io::stdout(),
+    )
+    .unwrap();
+}
+
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --release --bin d11 -- replay [--plain] [--delay <ms>] [--from <n>]
+    //     [--to <n>] [--ppm <directory>] [--scale <pixels>]
+    if args.get(1).map(String::as_str) == Some("replay") {
+        replay_cavern(ACTUAL_INPUT, &ReplayOptions::parse_args(&args[2..]));
+        return;
+    }
+
+    // cargo run --bin d11 -- history <steps> [threshold]
+    if args.get(1).map(String::as_str) == Some("history") {
+        let steps = args.get(2).expect("Missing steps").parse().unwrap();
+        let threshold = args.get(3).map_or(9, |arg| arg.parse().unwrap());
+        let mut cavern = Cavern::with_threshold(ACTUAL_INPUT, threshold);
+
+        cavern
+            .record_history(steps)
+            .iter()
+            .enumerate()
+            .for_each(|(i, record)| {
+                println!("Step {}: {} flashed", i + 1, record.count_flashed());
+                record.waves.iter().enumerate().for_each(|(wave, cells)| {
+                    println!("  wave {}: {:?}", wave, cells);
+                });
+            });
+        return;
+    }
+
+    // cargo run --bin d11 -- period [threshold]
+    if args.get(1).map(String::as_str) == Some("period") {
+        let threshold = args.get(2).map_or(9, |arg| arg.parse().unwrap());
+        match Cavern::with_threshold(ACTUAL_INPUT, threshold).find_periodicity(100_000) {
+            Some(cycle) => println!(
+                "periodic from step {}, with period {} (phase {})",
+                cycle.start,
+                cycle.period,
+                cycle.start % cycle.period
+            ),
+            None => println!("no period found"),
+        }
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -194,6 +330,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_step() {
+        let mut cavern = Cavern::from_input("11111\n19991\n19191\n19991\n11111");
+
+        assert_eq!(cavern.step(), 9);
+        assert_eq!(
+            cavern
+                .automaton
+                .grid()
+                .rows()
+                .map(|row| row
+                    .iter()
+                    .map(|octopus| match octopus {
+                        Octopus::Energy(energy) => char::from_digit(*energy as u32, 10).unwrap(),
+                        _ => '0',
+                    })
+                    .collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["34543", "40004", "50005", "40004", "34543"]
+        );
+
+        assert_eq!(cavern.step(), 0);
+    }
+
+    #[test]
+    fn test_step_with_record() {
+        let mut cavern = Cavern::from_input("11111\n19991\n19191\n19991\n11111");
+        let record = cavern.step_with_record();
+
+        // the 9s flash first, which sets off the 1 in the middle
+        assert_eq!(
+            record.waves,
+            vec![
+                vec![
+                    (1, 1),
+                    (1, 2),
+                    (1, 3),
+                    (2, 1),
+                    (2, 3),
+                    (3, 1),
+                    (3, 2),
+                    (3, 3)
+                ],
+                vec![(2, 2)]
+            ]
+        );
+        assert_eq!(cavern.step_with_record().waves, Vec::<Vec<_>>::new());
+    }
+
+    #[test]
+    fn test_history() {
+        let mut cavern = Cavern::from_input(SAMPLE_INPUT);
+        let history = cavern.record_history(195);
+
+        assert_eq!(
+            history
+                .iter()
+                .take(100)
+                .map(StepRecord::count_flashed)
+                .sum::<usize>(),
+            1656
+        );
+        assert_eq!(history[194].count_flashed(), 100);
+        assert!(history[..194]
+            .iter()
+            .all(|record| record.count_flashed() < 100));
+    }
+
+    #[test]
+    fn test_find_periodicity() {
+        // once they're synchronised, they all flash every 10 steps
+        assert_eq!(
+            Cavern::from_input(SAMPLE_INPUT).find_periodicity(1000),
+            Some(Cycle {
+                start: 195,
+                period: 10
+            })
+        );
+        // a single octopus, flashing every 4 steps
+        assert_eq!(
+            Cavern::with_threshold("0", 3).find_periodicity(100),
+            Some(Cycle {
+                start: 0,
+                period: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_threshold_and_size() {
+        let mut cavern = Cavern::with_threshold("1234\n0000", 3);
+        let record = cavern.step_with_record();
+
+        // the 3 and 4 flash, and the cascade goes around the grid from there
+        assert_eq!(
+            record.waves,
+            vec![
+                vec![(0, 2), (0, 3)],
+                vec![(0, 1)],
+                vec![(1, 2)],
+                vec![(1, 1), (1, 3)],
+                vec![(0, 0)],
+                vec![(1, 0)]
+            ]
+        );
+    }
+
     const SAMPLE_INPUT: &str = r"
 5483143223
 2745854711
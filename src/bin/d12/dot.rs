@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use super::{Graph, Vertex};
+
+// the name of every cave, quoted, sorted and with its style
+fn get_nodes(graph: &Graph) -> Vec<String> {
+    let mut vertices = graph.vertices.keys().collect::<Vec<_>>();
+    vertices.sort_by_key(|vertex| graph.get_name(vertex));
+
+    vertices
+        .into_iter()
+        .map(|vertex| {
+            let style = match vertex {
+                Vertex::Start | Vertex::End => "shape=doublecircle",
+                Vertex::Big(_) => "shape=box, style=filled, fillcolor=lightblue",
+                Vertex::Small(_) => "shape=ellipse",
+            };
+            format!("    \"{}\" [{}];", graph.get_name(vertex), style)
+        })
+        .collect()
+}
+
+// every edge once, with its caves in order
+fn get_edges(graph: &Graph) -> Vec<(&str, &str)> {
+    let mut edges = graph
+        .vertices
+        .iter()
+        .flat_map(|(a, neighbours)| {
+            neighbours
+                .iter()
+                .map(move |b| (graph.get_name(a), graph.get_name(b)))
+        })
+        .filter(|(a, b)| a < b)
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges
+}
+
+fn wrap(graph: &Graph, edges: Vec<String>) -> String {
+    let mut lines = vec!["graph caves {".to_string()];
+    lines.extend(get_nodes(graph));
+    lines.extend(edges);
+    lines.push("}".to_string());
+    lines.join("\n") + "\n"
+}
+
+pub fn to_dot(graph: &Graph) -> String {
+    let edges = get_edges(graph)
+        .into_iter()
+        .map(|(a, b)| format!("    \"{}\" -- \"{}\";", a, b))
+        .collect();
+    wrap(graph, edges)
+}
+
+// every edge labelled with how many paths use it, thicker the more it's used,
+// and greyed out if it's never used
+pub fn to_dot_with_usage(graph: &Graph, usage: &HashMap<(String, String), u64>) -> String {
+    let max_usage = usage.values().copied().max().unwrap_or(0).max(1);
+
+    let edges = get_edges(graph)
+        .into_iter()
+        .map(
+            |(a, b)| match usage.get(&(a.to_string(), b.to_string())).copied() {
+                Some(count) if count > 0 => format!(
+                    "    \"{}\" -- \"{}\" [label=\"{}\", penwidth={:.2}, color=red];",
+                    a,
+                    b,
+                    count,
+                    1.0 + 4.0 * count as f64 / max_usage as f64
+                ),
+                _ => format!("    \"{}\" -- \"{}\" [style=dashed, color=grey];", a, b),
+            },
+        )
+        .collect();
+    wrap(graph, edges)
+}
+
+// the edges of a graph written by `to_dot`, or any other undirected graph
+// with one `a -- b` edge per line, back into the puzzle input format
+pub fn from_dot(dot: &str) -> Graph {
+    let input = dot
+        .lines()
+        .filter_map(|line| line.split_once("--"))
+        .map(|(a, b)| {
+            let unquote = |name: &str| {
+                name.split('[')
+                    .next()
+                    .unwrap()
+                    .trim()
+                    .trim_end_matches(';')
+                    .trim()
+                    .trim_matches('"')
+                    .to_string()
+            };
+            format!("{}-{}", unquote(a), unquote(b))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if input.is_empty() {
+        panic!("Expected at least one edge");
+    }
+
+    Graph::from_input(&input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::PathFinder;
+
+    const SMALL_SAMPLE: &str = r"
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+";
+
+    #[test]
+    fn test_to_dot() {
+        assert_eq!(
+            to_dot(&Graph::from_input(SMALL_SAMPLE)),
+            r#"graph caves {
+    "A" [shape=box, style=filled, fillcolor=lightblue];
+    "b" [shape=ellipse];
+    "c" [shape=ellipse];
+    "d" [shape=ellipse];
+    "end" [shape=doublecircle];
+    "start" [shape=doublecircle];
+    "A" -- "b";
+    "A" -- "c";
+    "A" -- "end";
+    "A" -- "start";
+    "b" -- "d";
+    "b" -- "end";
+    "b" -- "start";
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_dot_with_usage() {
+        let graph = Graph::from_input(SMALL_SAMPLE);
+        let dot = to_dot_with_usage(&graph, &PathFinder::new(&graph).get_edge_usage(0));
+
+        assert!(dot.contains("\"b\" -- \"d\" [style=dashed, color=grey];"));
+        assert!(dot.contains("\"A\" -- \"start\" [label=\"7\", "));
+        assert!(dot.contains("\"b\" -- \"start\" [label=\"3\", "));
+    }
+
+    #[test]
+    fn test_from_dot() {
+        let graph = Graph::from_input(SMALL_SAMPLE);
+        let round_tripped = from_dot(&to_dot(&graph));
+
+        assert_eq!(to_dot(&round_tripped), to_dot(&graph));
+        assert_eq!(PathFinder::new(&round_tripped).count_paths(1), 36);
+    }
+}
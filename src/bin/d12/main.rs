@@ -1,8 +1,13 @@
+mod dot;
+mod paths;
+
 use std::collections::{HashMap, HashSet};
 
+use paths::PathFinder;
+
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Vertex {
     Start,
     End,
@@ -12,6 +17,7 @@ enum Vertex {
 
 struct Graph {
     vertices: HashMap<Vertex, HashSet<Vertex>>,
+    names: HashMap<Vertex, String>,
 }
 
 impl Graph {
@@ -24,6 +30,7 @@ impl Graph {
 
         let mut name_to_vertex_mapping = HashMap::new();
         let mut vertices = HashMap::new();
+        let mut names = HashMap::new();
 
         lines
             .iter()
@@ -44,6 +51,7 @@ impl Graph {
 
                     name_to_vertex_mapping.insert(name, vertex);
                     vertices.insert(vertex, HashSet::new());
+                    names.insert(vertex, name.to_string());
                 }
             });
 
@@ -54,120 +62,70 @@ impl Graph {
             vertices.get_mut(b).unwrap().insert(a.to_owned());
         });
 
-        Self { vertices }
-    }
-
-    fn is_big_cave(vertex: &str) -> bool {
-        vertex.chars().next().unwrap().is_ascii_uppercase()
-    }
-
-    fn p1_count_paths_to_end(&self, current: &Vertex, visited_smalls: &mut HashSet<i32>) -> usize {
-        if matches!(current, Vertex::End) {
-            1
-        } else {
-            self.vertices
-                .get(current)
-                .unwrap()
-                .iter()
-                .map(|neighbour| match neighbour {
-                    Vertex::End | Vertex::Big(_) => {
-                        self.p1_count_paths_to_end(neighbour, visited_smalls)
-                    }
-                    Vertex::Start => 0,
-                    Vertex::Small(index) => {
-                        if !visited_smalls.contains(index) {
-                            visited_smalls.insert(*index);
-                            let count = self.p1_count_paths_to_end(neighbour, visited_smalls);
-                            visited_smalls.remove(index);
-                            count
-                        } else {
-                            0
-                        }
-                    }
-                })
-                .sum::<usize>()
-        }
+        Self { vertices, names }
     }
 
-    fn p1_count_total_paths(&self) -> usize {
-        self.p1_count_paths_to_end(&Vertex::Start, &mut HashSet::new())
+    fn get_name(&self, vertex: &Vertex) -> &str {
+        &self.names[vertex]
     }
 
-    // changing visited_twice_small from Option<String> to &Option<String>
-    // (no reference -> reference) improved the performance of the p2() method
-    // for actual input.
-    //
-    // on test_p2_actual(), the timings before were:
-    //      1.33s, 1.34s, 1.33s, 1.33s, 1.36s
-    // and after the change:
-    //      1.26s, 1.26s, 1.28s, 1.27s, 1.27s
-    //
-    // then by optimizing further, we replace String -> i32, to avoid needless
-    // copy of string
-    //
-    // on test_p2_actual(), the new timings are:
-    //      0.75s, 0.73s, 0.73s, 0.75s, 0.75s
-    //
-    // all timings are in debug profile
-    fn p2_count_paths_to_end(
-        &self,
-        current: &Vertex,
-        visited_once_smalls: &mut HashSet<i32>,
-        visited_twice_small: &Option<i32>,
-    ) -> usize {
-        if matches!(current, Vertex::End) {
-            1
-        } else {
-            self.vertices
-                .get(current)
-                .unwrap()
-                .iter()
-                .map(|neighbour| match neighbour {
-                    Vertex::End | Vertex::Big(_) => self.p2_count_paths_to_end(
-                        neighbour,
-                        visited_once_smalls,
-                        visited_twice_small,
-                    ),
-                    Vertex::Start => 0,
-                    Vertex::Small(index) => {
-                        if !visited_once_smalls.contains(index) {
-                            visited_once_smalls.insert(*index);
-                            let count = self.p2_count_paths_to_end(
-                                neighbour,
-                                visited_once_smalls,
-                                visited_twice_small,
-                            );
-                            visited_once_smalls.remove(index);
-                            count
-                        } else if visited_twice_small.is_none() {
-                            self.p2_count_paths_to_end(
-                                neighbour,
-                                visited_once_smalls,
-                                &Some(*index),
-                            )
-                        } else {
-                            0
-                        }
-                    }
-                })
-                .sum::<usize>()
-        }
-    }
-
-    fn p2_count_total_paths(&self) -> usize {
-        self.p2_count_paths_to_end(&Vertex::Start, &mut HashSet::new(), &None)
+    fn is_big_cave(vertex: &str) -> bool {
+        vertex.chars().next().unwrap().is_ascii_uppercase()
     }
 }
 
 fn p1(input: &str) -> String {
-    Graph::from_input(input).p1_count_total_paths().to_string()
+    PathFinder::new(&Graph::from_input(input))
+        .count_paths(0)
+        .to_string()
 }
 
 fn p2(input: &str) -> String {
-    Graph::from_input(input).p2_count_total_paths().to_string()
+    PathFinder::new(&Graph::from_input(input))
+        .count_paths(1)
+        .to_string()
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d12 -- dot [--usage <revisits>] [--from <file>]
+    if args.get(1).map(String::as_str) == Some("dot") {
+        let graph = match args.iter().position(|arg| arg == "--from") {
+            Some(i) => dot::from_dot(
+                &std::fs::read_to_string(args.get(i + 1).expect("Missing file")).unwrap(),
+            ),
+            None => Graph::from_input(ACTUAL_INPUT),
+        };
+
+        match args.iter().position(|arg| arg == "--usage") {
+            Some(i) => {
+                let revisits = args.get(i + 1).expect("Missing revisits").parse().unwrap();
+                let usage = PathFinder::new(&graph).get_edge_usage(revisits);
+                print!("{}", dot::to_dot_with_usage(&graph, &usage));
+            }
+            None => print!("{}", dot::to_dot(&graph)),
+        }
+        return;
+    }
+
+    // cargo run --bin d12 -- paths <revisits> [--list]
+    if args.get(1).map(String::as_str) == Some("paths") {
+        let revisits = args.get(2).expect("Missing revisits").parse().unwrap();
+        let graph = Graph::from_input(ACTUAL_INPUT);
+        let path_finder = PathFinder::new(&graph);
+
+        if args.iter().any(|arg| arg == "--list") {
+            path_finder
+                .enumerate_paths(revisits)
+                .iter()
+                .for_each(|path| println!("{}", path));
+        } else {
+            println!("{}", path_finder.count_paths(revisits));
+        }
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
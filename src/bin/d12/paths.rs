@@ -0,0 +1,355 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Graph, Vertex};
+
+// (cave, small caves visited so far, revisits left)
+type State = (usize, u64, usize);
+
+// the caves are numbered, so the visited small caves fit in a bitmask
+pub struct PathFinder {
+    names: Vec<String>,
+    vertices: Vec<Vertex>,
+    neighbours: Vec<Vec<usize>>,
+    // the bit of every small cave in the visited mask
+    small_bits: Vec<Option<u64>>,
+    start: usize,
+    end: usize,
+}
+
+impl PathFinder {
+    pub fn new(graph: &Graph) -> Self {
+        // sorted by name, so the paths come out in the same order every time
+        let mut vertices = graph.vertices.keys().copied().collect::<Vec<_>>();
+        vertices.sort_by_key(|vertex| graph.get_name(vertex).to_string());
+
+        let index_of = |vertex: &Vertex| vertices.iter().position(|v| v == vertex).unwrap();
+
+        let mut total_small = 0;
+        let small_bits = vertices
+            .iter()
+            .map(|vertex| match vertex {
+                Vertex::Small(_) => {
+                    total_small += 1;
+                    if total_small > 64 {
+                        panic!("Expected at most 64 small caves");
+                    }
+                    Some(1 << (total_small - 1))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let neighbours = vertices
+            .iter()
+            .map(|vertex| {
+                let mut neighbours = graph.vertices[vertex]
+                    .iter()
+                    .map(index_of)
+                    .collect::<Vec<_>>();
+                neighbours.sort_unstable();
+                neighbours
+            })
+            .collect();
+
+        Self {
+            names: vertices
+                .iter()
+                .map(|vertex| graph.get_name(vertex).to_string())
+                .collect(),
+            start: index_of(&Vertex::Start),
+            end: index_of(&Vertex::End),
+            neighbours,
+            small_bits,
+            vertices,
+        }
+    }
+
+    // where the path can go next from `current`, along with the visited
+    // mask and the revisits left after the move
+    fn get_moves(
+        &self,
+        current: usize,
+        visited: u64,
+        revisits: usize,
+    ) -> impl Iterator<Item = (usize, u64, usize)> + '_ {
+        self.neighbours[current]
+            .iter()
+            .filter_map(move |&neighbour| match self.vertices[neighbour] {
+                Vertex::Start => None,
+                Vertex::End | Vertex::Big(_) => Some((neighbour, visited, revisits)),
+                Vertex::Small(_) => {
+                    let bit = self.small_bits[neighbour].unwrap();
+                    if visited & bit == 0 {
+                        Some((neighbour, visited | bit, revisits))
+                    } else if revisits > 0 {
+                        Some((neighbour, visited, revisits - 1))
+                    } else {
+                        None
+                    }
+                }
+            })
+    }
+
+    fn count_from(
+        &self,
+        current: usize,
+        visited: u64,
+        revisits: usize,
+        cache: &mut HashMap<(usize, u64, usize), u64>,
+    ) -> u64 {
+        if current == self.end {
+            return 1;
+        }
+
+        if let Some(count) = cache.get(&(current, visited, revisits)) {
+            return *count;
+        }
+
+        let count = self
+            .get_moves(current, visited, revisits)
+            .map(|(next, visited, revisits)| self.count_from(next, visited, revisits, cache))
+            .sum();
+        cache.insert((current, visited, revisits), count);
+        count
+    }
+
+    // small caves other than start and end can be visited a second (or
+    // third, ...) time, `revisits` times in total across the path
+    pub fn count_paths(&self, revisits: usize) -> u64 {
+        self.count_from(self.start, 0, revisits, &mut HashMap::new())
+    }
+
+    fn enumerate_from(
+        &self,
+        path: &mut Vec<usize>,
+        visited: u64,
+        revisits: usize,
+        paths: &mut Vec<Vec<usize>>,
+    ) {
+        let current = *path.last().unwrap();
+        if current == self.end {
+            paths.push(path.clone());
+            return;
+        }
+
+        self.get_moves(current, visited, revisits)
+            .for_each(|(next, visited, revisits)| {
+                path.push(next);
+                self.enumerate_from(path, visited, revisits, paths);
+                path.pop();
+            });
+    }
+
+    // every path as the indices of its caves
+    pub fn enumerate_indices(&self, revisits: usize) -> Vec<Vec<usize>> {
+        let mut paths = vec![];
+        self.enumerate_from(&mut vec![self.start], 0, revisits, &mut paths);
+        paths
+    }
+
+    // the names of the caves of an edge, in order
+    fn get_edge_key(&self, a: usize, b: usize) -> (String, String) {
+        let (a, b) = (&self.names[a], &self.names[b]);
+        if a < b {
+            (a.clone(), b.clone())
+        } else {
+            (b.clone(), a.clone())
+        }
+    }
+
+    // every state reachable from `state`, each one after all of the states
+    // reachable from it (the moves never lead back to a state, as long as
+    // no two big caves are connected)
+    fn collect_states(&self, state: State, seen: &mut HashSet<State>, order: &mut Vec<State>) {
+        if !seen.insert(state) {
+            return;
+        }
+
+        let (current, visited, revisits) = state;
+        if current != self.end {
+            self.get_moves(current, visited, revisits)
+                .for_each(|next| self.collect_states(next, seen, order));
+        }
+        order.push(state);
+    }
+
+    // how many times the paths go along every edge, either way, keyed on the
+    // names of its caves in order; a move between two states is made by
+    // every path to the first state, followed by every path from the second
+    pub fn get_edge_usage(&self, revisits: usize) -> HashMap<(String, String), u64> {
+        let start = (self.start, 0, revisits);
+        let mut order = vec![];
+        self.collect_states(start, &mut HashSet::new(), &mut order);
+
+        let mut cache = HashMap::new();
+        let mut paths_to = HashMap::from([(start, 1)]);
+        let mut usage = HashMap::new();
+
+        // every state comes before the states it leads to
+        order
+            .into_iter()
+            .rev()
+            .for_each(|(current, visited, revisits)| {
+                if current == self.end {
+                    return;
+                }
+
+                let to_here = paths_to[&(current, visited, revisits)];
+                self.get_moves(current, visited, revisits).for_each(|next| {
+                    *paths_to.entry(next).or_insert(0) += to_here;
+
+                    let from_there = self.count_from(next.0, next.1, next.2, &mut cache);
+                    if from_there > 0 {
+                        *usage.entry(self.get_edge_key(current, next.0)).or_insert(0) +=
+                            to_here * from_there;
+                    }
+                });
+            });
+
+        usage
+    }
+
+    // every path as "start,A,b,end", sorted
+    pub fn enumerate_paths(&self, revisits: usize) -> Vec<String> {
+        let mut paths = self
+            .enumerate_indices(revisits)
+            .into_iter()
+            .map(|path| {
+                path.into_iter()
+                    .map(|i| self.names[i].as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_SAMPLE: &str = r"
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+";
+
+    const LARGEST_SAMPLE: &str = r"
+fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW
+";
+
+    #[test]
+    fn test_enumerate_paths() {
+        let path_finder = PathFinder::new(&Graph::from_input(SMALL_SAMPLE));
+
+        assert_eq!(
+            path_finder.enumerate_paths(0),
+            vec![
+                "start,A,b,A,c,A,end",
+                "start,A,b,A,end",
+                "start,A,b,end",
+                "start,A,c,A,b,A,end",
+                "start,A,c,A,b,end",
+                "start,A,c,A,end",
+                "start,A,end",
+                "start,b,A,c,A,end",
+                "start,b,A,end",
+                "start,b,end",
+            ]
+        );
+        assert_eq!(path_finder.enumerate_paths(1).len(), 36);
+        assert!(path_finder
+            .enumerate_paths(1)
+            .contains(&"start,A,b,A,b,A,c,A,end".to_string()));
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let graph = Graph::from_input(LARGEST_SAMPLE);
+        let path_finder = PathFinder::new(&graph);
+
+        assert_eq!(path_finder.count_paths(0), 226);
+        assert_eq!(path_finder.count_paths(1), 3509);
+
+        // counted and enumerated the same
+        (0..=3).for_each(|revisits| {
+            assert_eq!(
+                path_finder.count_paths(revisits) as usize,
+                path_finder.enumerate_paths(revisits).len()
+            );
+        });
+    }
+
+    #[test]
+    fn test_edge_usage() {
+        let path_finder = PathFinder::new(&Graph::from_input(SMALL_SAMPLE));
+        let usage = path_finder.get_edge_usage(0);
+
+        let get = |a: &str, b: &str| usage.get(&(a.to_string(), b.to_string())).copied();
+        // every path leaves start and reaches end once
+        assert_eq!(get("A", "start").unwrap() + get("b", "start").unwrap(), 10);
+        assert_eq!(get("A", "end").unwrap() + get("b", "end").unwrap(), 10);
+        // d is a dead end
+        assert_eq!(get("b", "d"), None);
+    }
+
+    #[test]
+    fn test_edge_usage_matches_paths() {
+        let path_finder = PathFinder::new(&Graph::from_input(LARGEST_SAMPLE));
+
+        (0..=2).for_each(|revisits| {
+            let mut expected = HashMap::new();
+            path_finder
+                .enumerate_indices(revisits)
+                .iter()
+                .for_each(|path| {
+                    path.windows(2).for_each(|pair| {
+                        *expected
+                            .entry(path_finder.get_edge_key(pair[0], pair[1]))
+                            .or_insert(0) += 1;
+                    })
+                });
+
+            assert_eq!(path_finder.get_edge_usage(revisits), expected);
+        });
+    }
+
+    #[test]
+    fn test_third_visit() {
+        // with two revisits, b can be visited three times
+        let path_finder = PathFinder::new(&Graph::from_input("start-A\nA-b\nA-end"));
+
+        assert_eq!(
+            path_finder.enumerate_paths(2),
+            vec![
+                "start,A,b,A,b,A,b,A,end",
+                "start,A,b,A,b,A,end",
+                "start,A,b,A,end",
+                "start,A,end"
+            ]
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use std::{collections::HashSet, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fold {
+    pub axis: Axis,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    // not "fold along <axis>=<position>"
+    Malformed(String),
+    UnknownAxis(String),
+    // not a whole number, or negative
+    Position(String),
+    // not "<x>,<y>"
+    Dot(String),
+    // a dot that a fold would have to put on top of the crease
+    Crease((i32, i32), Fold),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Malformed(line) => write!(f, "malformed fold '{}'", line),
+            Error::UnknownAxis(axis) => {
+                write!(f, "unknown fold axis '{}', expected x or y", axis)
+            }
+            Error::Position(position) => write!(f, "invalid fold position '{}'", position),
+            Error::Dot(line) => write!(f, "malformed dot '{}'", line),
+            Error::Crease((x, y), fold) => {
+                write!(f, "dot {},{} is on the crease of {}", x, y, fold)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Fold {
+    // "fold along x=5"
+    pub fn parse(line: &str) -> Result<Self, Error> {
+        let (axis, position) = line
+            .strip_prefix("fold along ")
+            .and_then(|line| line.split_once('='))
+            .ok_or_else(|| Error::Malformed(line.to_string()))?;
+
+        let axis = match axis {
+            "x" => Axis::X,
+            "y" => Axis::Y,
+            _ => return Err(Error::UnknownAxis(axis.to_string())),
+        };
+
+        match position.parse() {
+            Ok(position) if position >= 0 => Ok(Self { axis, position }),
+            _ => Err(Error::Position(position.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Fold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let axis = match self.axis {
+            Axis::X => "x",
+            Axis::Y => "y",
+        };
+        write!(f, "fold along {}={}", axis, self.position)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    pub points: HashSet<(i32, i32)>,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Sheet {
+    // just big enough for every dot, and grown by the folds if the paper
+    // turns out to be bigger
+    pub fn new(points: HashSet<(i32, i32)>) -> Self {
+        let width = points.iter().map(|point| point.0 + 1).max().unwrap_or(0);
+        let height = points.iter().map(|point| point.1 + 1).max().unwrap_or(0);
+
+        Self {
+            points,
+            width,
+            height,
+        }
+    }
+
+    // the part past the crease is folded back over the rest; when it's the
+    // larger part, it sticks out past the edge at 0, so everything is shifted
+    // to keep the coordinates from going negative. The paper is at least
+    // big enough to have the crease in its middle, even when no dot is past
+    // the crease
+    pub fn fold(&self, fold: &Fold) -> Result<Sheet, Error> {
+        let position = fold.position;
+        if position < 0 {
+            return Err(Error::Position(position.to_string()));
+        }
+
+        let length = match fold.axis {
+            Axis::X => self.width,
+            Axis::Y => self.height,
+        }
+        .max(2 * position + 1);
+
+        let folded_length = length - 1 - position;
+        let shift = (folded_length - position).max(0);
+
+        let fold_coord = |coord: i32| match coord.cmp(&position) {
+            std::cmp::Ordering::Less => Some(coord + shift),
+            std::cmp::Ordering::Greater => Some(2 * position - coord + shift),
+            std::cmp::Ordering::Equal => None,
+        };
+
+        let points = self
+            .points
+            .iter()
+            .map(|&(x, y)| {
+                match fold.axis {
+                    Axis::X => fold_coord(x).map(|x| (x, y)),
+                    Axis::Y => fold_coord(y).map(|y| (x, y)),
+                }
+                .ok_or(Error::Crease((x, y), *fold))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let new_length = position.max(folded_length);
+        Ok(match fold.axis {
+            Axis::X => Sheet {
+                points,
+                width: new_length,
+                height: self.height,
+            },
+            Axis::Y => Sheet {
+                points,
+                width: self.width,
+                height: new_length,
+            },
+        })
+    }
+
+    // the whole sheet, including the rows and columns without any dots
+    pub fn render(&self) -> String {
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| {
+                        if self.points.contains(&(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn parse_dot(line: &str) -> Result<(i32, i32), Error> {
+    line.split_once(',')
+        .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
+        .filter(|(x, y)| *x >= 0 && *y >= 0)
+        .ok_or_else(|| Error::Dot(line.to_string()))
+}
+
+pub fn parse_input(input: &str) -> Result<(Sheet, Vec<Fold>), Error> {
+    let points = input
+        .trim()
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with("fold"))
+        .map(parse_dot)
+        .collect::<Result<HashSet<_>, _>>()?;
+
+    let folds = input
+        .trim()
+        .lines()
+        .filter(|line| line.starts_with("fold"))
+        .map(Fold::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((Sheet::new(points), folds))
+}
+
+// the sheet before any fold, then after every one of them
+pub fn replay(sheet: &Sheet, folds: &[Fold]) -> Result<Vec<Sheet>, Error> {
+    let mut sheets = vec![sheet.clone()];
+    folds.iter().try_for_each(|fold| {
+        let next = sheets.last().unwrap().fold(fold)?;
+        sheets.push(next);
+        Ok(())
+    })?;
+    Ok(sheets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_sheet(picture: &str) -> Sheet {
+        let rows = picture.trim().lines().collect::<Vec<_>>();
+        Sheet {
+            points: rows
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.chars()
+                        .enumerate()
+                        .filter(|(_, c)| *c == '#')
+                        .map(move |(x, _)| (x as i32, y as i32))
+                })
+                .collect(),
+            width: rows[0].len() as i32,
+            height: rows.len() as i32,
+        }
+    }
+
+    #[test]
+    fn test_fold_parse() {
+        let fold = Fold::parse("fold along y=7").unwrap();
+        assert_eq!(
+            fold,
+            Fold {
+                axis: Axis::Y,
+                position: 7
+            }
+        );
+        assert_eq!(fold.to_string(), "fold along y=7");
+    }
+
+    #[test]
+    fn test_fold_parse_errors() {
+        assert_eq!(
+            Fold::parse("fold along z=7"),
+            Err(Error::UnknownAxis("z".to_string()))
+        );
+        assert_eq!(
+            Fold::parse("fold x=7"),
+            Err(Error::Malformed("fold x=7".to_string()))
+        );
+        assert_eq!(
+            Fold::parse("fold along x=-1"),
+            Err(Error::Position("-1".to_string()))
+        );
+        assert_eq!(
+            parse_input("1;2\n\nfold along x=1").err(),
+            Some(Error::Dot("1;2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_fold_midpoint() {
+        let sheet = create_sheet("#...#\n.#..#");
+        let fold = Fold {
+            axis: Axis::X,
+            position: 2,
+        };
+
+        assert_eq!(sheet.fold(&fold).unwrap().render(), "#.\n##");
+    }
+
+    #[test]
+    fn test_fold_smaller_part() {
+        // the last column lands on the one just before the crease
+        let sheet = create_sheet("#...#.#");
+        let fold = Fold {
+            axis: Axis::X,
+            position: 5,
+        };
+
+        assert_eq!(sheet.fold(&fold).unwrap().render(), "#...#");
+    }
+
+    #[test]
+    fn test_fold_larger_part() {
+        // the 5 rows below the crease end up sticking out 3 rows above the
+        // 2 rows above it
+        let sheet = create_sheet("#.\n.#\n..\n#.\n..\n..\n..\n.#");
+        let fold = Fold {
+            axis: Axis::Y,
+            position: 2,
+        };
+        let folded = sheet.fold(&fold).unwrap();
+
+        assert_eq!((folded.width, folded.height), (2, 5));
+        assert_eq!(folded.render(), ".#\n..\n..\n#.\n##");
+    }
+
+    #[test]
+    fn test_fold_past_last_dot() {
+        // the paper is wider than the dots on it, so nothing lands on the
+        // left of the crease and the dot doesn't move
+        let (sheet, folds) = parse_input("0,0\n\nfold along x=2").unwrap();
+        let folded = sheet.fold(&folds[0]).unwrap();
+
+        assert_eq!(folded.points, HashSet::from([(0, 0)]));
+        assert_eq!((folded.width, folded.height), (2, 1));
+    }
+
+    #[test]
+    fn test_fold_errors() {
+        let sheet = create_sheet("#.#\n.#.");
+
+        assert_eq!(
+            sheet.fold(&Fold::parse("fold along x=1").unwrap()),
+            Err(Error::Crease(
+                (1, 1),
+                Fold::parse("fold along x=1").unwrap()
+            ))
+        );
+        assert_eq!(
+            sheet.fold(&Fold {
+                axis: Axis::X,
+                position: -1,
+            }),
+            Err(Error::Position("-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let (sheet, folds) =
+            parse_input("0,0\n3,0\n3,2\n\nfold along x=2\nfold along y=1").unwrap();
+        let sheets = replay(&sheet, &folds).unwrap();
+
+        assert_eq!(
+            sheets.iter().map(Sheet::render).collect::<Vec<_>>(),
+            vec!["#..#\n....\n...#", "##\n..\n.#", "##"]
+        );
+    }
+
+    #[test]
+    fn test_replay_empty_edges() {
+        // the folds leave an empty last column and an empty last row, which
+        // are still part of the sheet
+        let (sheet, folds) = parse_input("0,0\n\nfold along x=2\nfold along y=2").unwrap();
+        let sheets = replay(&sheet, &folds).unwrap();
+        let last = sheets.last().unwrap();
+
+        assert_eq!((last.width, last.height), (2, 2));
+        assert_eq!(last.render(), "#.\n..");
+    }
+
+    #[test]
+    fn test_replay_crease() {
+        let (sheet, folds) = parse_input("0,0\n0,2\n\nfold along x=3\nfold along y=2").unwrap();
+
+        assert_eq!(replay(&sheet, &folds), Err(Error::Crease((0, 2), folds[1])));
+    }
+}
@@ -1,88 +1,62 @@
+mod folding;
+
 use std::collections::HashSet;
 
+use advent_of_code_2021_rust::ocr;
+
 const ACTUAL_INPUT: &str = include_str!("input.txt");
 
+// only as big as the dots, for the OCR
 fn get_points_display(points: &HashSet<(i32, i32)>) -> String {
     if points.is_empty() {
         return "Empty".to_owned();
     }
 
-    // String native type can be used as a buffer efficiently
-    let mut string_buffer = String::new();
-    let max_x = points.iter().map(|point| point.0).max().unwrap();
-    let max_y = points.iter().map(|point| point.1).max().unwrap();
-
-    string_buffer.push('\n'); // for easier testing, add \n at the start
-    for y in 0..=max_y {
-        for x in 0..=max_x {
-            string_buffer.push(if points.contains(&(x, y)) { '#' } else { '.' });
-        }
-        string_buffer.push('\n');
-    }
-    string_buffer
+    // for easier testing, add \n at the start
+    format!("\n{}\n", folding::Sheet::new(points.clone()).render())
 }
 
-fn solve(input: &str) -> (usize, String) {
-    let mut points = input
-        .trim()
-        .lines()
-        .filter(|line| !line.is_empty() && !line.starts_with("fold"))
-        .map(|line| {
-            let (x, y) = line.split_once(',').unwrap();
-            (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap())
-        })
-        .collect::<HashSet<_>>();
-
-    let mut first_fold_dots_count = None;
-
-    input
-        .trim()
-        .lines()
-        .filter(|line| line.starts_with("fold"))
-        .for_each(|line| {
-            let line = line.strip_prefix("fold along ").unwrap();
-            let (direction, position) = line.split_once('=').unwrap();
-            let position = position.parse::<i32>().unwrap();
-
-            #[allow(clippy::needless_collect)] // reason = "collect is actually needed"
-            let points_to_remove = points
-                .iter()
-                .filter(|point| match direction {
-                    "y" => point.1 > position,
-                    "x" => point.0 > position,
-                    _ => panic!("Unknown direction {}", direction),
-                })
-                .copied()
-                .collect::<Vec<_>>();
-
-            points_to_remove.into_iter().for_each(|point| {
-                let new_point = match direction {
-                    "y" => (point.0, 2 * position - point.1),
-                    "x" => (2 * position - point.0, point.1),
-                    _ => panic!("Unknown direction {}", direction),
-                };
-
-                points.remove(&point);
-                points.insert(new_point);
-            });
-
-            if first_fold_dots_count.is_none() {
-                first_fold_dots_count = Some(points.len());
-            }
-        });
+fn solve(input: &str) -> Result<(usize, String), folding::Error> {
+    let (sheet, folds) = folding::parse_input(input)?;
+    let sheets = folding::replay(&sheet, &folds)?;
 
-    (first_fold_dots_count.unwrap(), get_points_display(&points))
+    Ok((
+        sheets[1].points.len(),
+        get_points_display(&sheets.last().unwrap().points),
+    ))
 }
 
 fn p1(input: &str) -> String {
-    solve(input).0.to_string()
+    let (dots, _) = solve(input).unwrap_or_else(|error| panic!("Invalid input: {}", error));
+    dots.to_string()
 }
 
 fn p2(input: &str) -> String {
-    solve(input).1
+    let (_, picture) = solve(input).unwrap_or_else(|error| panic!("Invalid input: {}", error));
+    ocr::read_picture(&picture).unwrap()
 }
 
 fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+
+    // cargo run --bin d13 -- replay [file]
+    if args.get(1).map(String::as_str) == Some("replay") {
+        let input = args.get(2).map_or(ACTUAL_INPUT.to_string(), |file| {
+            std::fs::read_to_string(file).unwrap()
+        });
+        let (sheet, folds) =
+            folding::parse_input(&input).unwrap_or_else(|error| panic!("Invalid input: {}", error));
+        let sheets = folding::replay(&sheet, &folds)
+            .unwrap_or_else(|error| panic!("Invalid input: {}", error));
+
+        sheets.iter().enumerate().skip(1).for_each(|(i, sheet)| {
+            println!("After {} ({}x{})", folds[i - 1], sheet.width, sheet.height);
+            println!("{}", sheet.render());
+            println!();
+        });
+        return;
+    }
+
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
 }
@@ -127,8 +101,12 @@ fold along x=5
 
     #[test]
     fn test_p2_sample() {
+        let picture = solve(SAMPLE_INPUT).unwrap().1;
+
+        // a square rather than letters
+        assert_eq!(ocr::read_picture(&picture), Err(ocr::OcrError::Height(5)));
         assert_eq!(
-            p2(SAMPLE_INPUT),
+            picture,
             r"
 #####
 #...#
@@ -141,8 +119,9 @@ fold along x=5
 
     #[test]
     fn test_p2_actual() {
+        assert_eq!(p2(ACTUAL_INPUT), "EPUELPBR");
         assert_eq!(
-            p2(ACTUAL_INPUT),
+            solve(ACTUAL_INPUT).unwrap().1,
             r"
 ####.###..#..#.####.#....###..###..###.
 #....#..#.#..#.#....#....#..#.#..#.#..#
@@ -0,0 +1,328 @@
+use advent_of_code_2021_rust::automaton::Grid;
+
+use crate::engine::parse_input;
+
+const ACTUAL_INPUT: &str = include_str!("input.txt");
+
+// the image is stored row by row, with every row packed into u64 words
+// (bit x % 64 of word x / 64). Everything outside of the stored area has
+// the same state, which is tracked explicitly in `background`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DenseImage {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+    background: bool,
+}
+
+impl DenseImage {
+    fn new(width: usize, height: usize, background: bool) -> Self {
+        let words_per_row = width.div_ceil(64);
+
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+            background,
+        }
+    }
+
+    // packs the engine's grid, whose origin becomes (0, 0)
+    fn from_grid(grid: &Grid<bool>) -> Self {
+        let origin = grid.origin();
+        let mut result = Self::new(
+            grid.width(),
+            grid.height(),
+            grid.background().unwrap_or(false),
+        );
+
+        grid.iter()
+            .filter(|(_, lit)| *lit)
+            .for_each(|((x, y), _)| result.set((x - origin.0) as usize, (y - origin.1) as usize));
+
+        result
+    }
+
+    fn set(&mut self, x: usize, y: usize) {
+        self.words[y * self.words_per_row + x / 64] |= 1 << (x % 64);
+    }
+
+    // a single pixel, only needed to check the packed words
+    #[cfg(test)]
+    fn get(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            self.background
+        } else {
+            let (x, y) = (x as usize, y as usize);
+            (self.words[y * self.words_per_row + x / 64] >> (x % 64)) & 1 == 1
+        }
+    }
+
+    // the packed words of a row, or None outside of the stored area
+    fn get_row(&self, y: i64) -> Option<&[u64]> {
+        if y < 0 || y >= self.height as i64 {
+            None
+        } else {
+            let start = y as usize * self.words_per_row;
+            Some(&self.words[start..start + self.words_per_row])
+        }
+    }
+
+    // the new image grows by one pixel on every side, since those are the
+    // only pixels outside the old image whose kernel can see the old image
+    //
+    // the 9-bit index slides to the right along with the kernel: every
+    // kernel row drops its leftmost bit, and takes the next bit on its right
+    // from a register holding the current word of that row, which is shifted
+    // one bit further for every pixel
+    fn enhance(&self, algorithm: &[bool]) -> Self {
+        let background = self.background as usize;
+        let mut result = Self::new(
+            self.width + 2,
+            self.height + 2,
+            algorithm[if self.background { 511 } else { 0 }],
+        );
+
+        (0..result.height).for_each(|new_y| {
+            // new (x, y) is centered on old (x - 1, y - 1)
+            let old_rows =
+                [new_y as i64 - 2, new_y as i64 - 1, new_y as i64].map(|old_y| self.get_row(old_y));
+            let mut registers = [0u64; 3];
+            let mut index = if self.background { 511 } else { 0 };
+
+            (0..result.width).for_each(|new_x| {
+                // the old column entering the kernel on the right is new_x
+                let column =
+                    old_rows
+                        .iter()
+                        .zip(registers.iter_mut())
+                        .fold(0, |column, (row, register)| {
+                            let bit = match row {
+                                Some(words) if new_x < self.width => {
+                                    if new_x % 64 == 0 {
+                                        *register = words[new_x / 64];
+                                    }
+                                    let bit = (*register & 1) as usize;
+                                    *register >>= 1;
+                                    bit
+                                }
+                                _ => background,
+                            };
+                            (column << 3) | bit
+                        });
+                index = ((index << 1) & 0b110_110_110) | column;
+
+                if algorithm[index] {
+                    result.set(new_x, new_y);
+                }
+            });
+        });
+
+        result
+    }
+
+    // None if the infinite background is lit
+    fn count_lit(&self) -> Option<u32> {
+        if self.background {
+            None
+        } else {
+            Some(self.words.iter().map(|word| word.count_ones()).sum())
+        }
+    }
+}
+
+fn solve(input: &str, total_iterations: usize) -> String {
+    let (algorithm, grid) = parse_input(input);
+
+    let image = (0..total_iterations).fold(DenseImage::from_grid(&grid), |image, _| {
+        image.enhance(&algorithm)
+    });
+
+    match image.count_lit() {
+        Some(count) => count.to_string(),
+        None => "infinity".to_string(),
+    }
+}
+
+fn p1(input: &str) -> String {
+    solve(input, 2)
+}
+
+fn p2(input: &str) -> String {
+    solve(input, 50)
+}
+
+pub fn solve_dense() {
+    println!("{}", p1(ACTUAL_INPUT));
+    println!("{}", p2(ACTUAL_INPUT));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{parse_algorithm, parse_rows};
+    use crate::sparse;
+    use advent_of_code_2021_rust::rng::Rng;
+    use std::collections::HashSet;
+
+    fn parse_image(image: &str) -> DenseImage {
+        DenseImage::from_grid(&Grid::infinite(parse_rows(image), false))
+    }
+
+    #[test]
+    fn test_parse_image() {
+        let image = parse_image("#..\n.#.\n..#");
+
+        assert_eq!((image.width, image.height), (3, 3));
+        assert!(image.get(0, 0));
+        assert!(!image.get(1, 0));
+        assert!(image.get(2, 2));
+        assert!(!image.get(-1, -1));
+        assert_eq!(image.count_lit(), Some(3));
+    }
+
+    #[test]
+    fn test_enhance_wide_image() {
+        // crosses the boundary between two words
+        let image = parse_image(&format!("{}#", ".".repeat(63)));
+
+        // every pixel with at least one lit pixel in its kernel is lit
+        let mut algorithm = vec![true; 512];
+        algorithm[0] = false;
+
+        let enhanced = image.enhance(&algorithm);
+        assert_eq!((enhanced.width, enhanced.height), (66, 3));
+        assert_eq!(enhanced.count_lit(), Some(9));
+        assert!(enhanced.get(65, 2));
+        assert!(!enhanced.get(62, 1));
+    }
+
+    #[test]
+    fn test_enhance_matches_kernel() {
+        let (algorithm, _) = parse_input(ACTUAL_INPUT);
+        let mut rng = Rng::new(0x2021_0020);
+
+        // wider than two words, so the registers are reloaded mid-row
+        let image = (0..7)
+            .map(|_| {
+                (0..150)
+                    .map(|_| if rng.below(3) == 0 { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // both backgrounds, since the actual algorithm flips it every time
+        (0..3).fold(parse_image(&image), |image, _| {
+            let enhanced = image.enhance(&algorithm);
+
+            (0..enhanced.height as i64).for_each(|y| {
+                (0..enhanced.width as i64).for_each(|x| {
+                    let index = (-1..=1)
+                        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                        .fold(0, |index, (dx, dy)| {
+                            (index << 1) | image.get(x - 1 + dx, y - 1 + dy) as usize
+                        });
+                    assert_eq!(enhanced.get(x, y), algorithm[index], "({}, {})", x, y);
+                });
+            });
+
+            enhanced
+        });
+    }
+
+    #[test]
+    fn test_enhance_matches_sparse() {
+        // the sparse backend only supports an unlit background, so the
+        // algorithm keeps the empty kernel unlit
+        let mut rng = Rng::new(0x2021_0029);
+        let mut algorithm = (0..512).map(|_| rng.below(2) == 0).collect::<Vec<_>>();
+        algorithm[0] = false;
+        let sparse_algorithm = algorithm
+            .iter()
+            .enumerate()
+            .filter(|(_, lit)| **lit)
+            .map(|(i, _)| i as i32)
+            .collect::<HashSet<_>>();
+
+        let image = (0..9)
+            .map(|_| {
+                (0..70)
+                    .map(|_| if rng.below(2) == 0 { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut dense = parse_image(&image);
+        let mut sparse = parse_rows(&image)
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, lit)| **lit)
+                    .map(move |(x, _)| (x as i32, y as i32))
+            })
+            .collect::<HashSet<_>>();
+
+        (1..=4).for_each(|iteration| {
+            dense = dense.enhance(&algorithm);
+            sparse = sparse::enhance(&sparse, &sparse_algorithm, iteration - 1);
+
+            // the dense image grows by one pixel on every side, so its
+            // origin moves one pixel up and to the left every time
+            let dense_lit = (0..dense.height as i64)
+                .flat_map(|y| (0..dense.width as i64).map(move |x| (x, y)))
+                .filter(|(x, y)| dense.get(*x, *y))
+                .map(|(x, y)| ((x - iteration as i64) as i32, (y - iteration as i64) as i32))
+                .collect::<HashSet<_>>();
+
+            assert_eq!(dense_lit, sparse, "iteration {}", iteration);
+        });
+    }
+
+    #[test]
+    fn test_background_flip() {
+        // the actual input lights up the empty kernel, and turns off
+        // the fully lit kernel
+        let (algorithm, image) = ACTUAL_INPUT.trim().split_once("\n\n").unwrap();
+        let algorithm = parse_algorithm(algorithm);
+        assert!(algorithm[0] && !algorithm[511]);
+
+        let image = parse_image(image).enhance(&algorithm);
+        assert!(image.background);
+        assert_eq!(image.count_lit(), None);
+        assert!(image.get(-100, -100));
+
+        let image = image.enhance(&algorithm);
+        assert!(!image.background);
+        assert_eq!(image.count_lit(), Some(4917));
+
+        assert_eq!(solve(ACTUAL_INPUT, 1), "infinity");
+    }
+
+    const SAMPLE_INPUT: &str = include_str!("example.txt");
+
+    #[test]
+    fn test_p1_sample() {
+        assert_eq!(p1(SAMPLE_INPUT), "35");
+    }
+
+    #[test]
+    fn test_p1_actual() {
+        assert_eq!(p1(ACTUAL_INPUT), "4917");
+    }
+
+    #[test]
+    fn test_p2_sample() {
+        assert_eq!(p2(SAMPLE_INPUT), "3351");
+    }
+
+    #[test]
+    fn test_p2_actual() {
+        assert_eq!(p2(ACTUAL_INPUT), "16389");
+    }
+}
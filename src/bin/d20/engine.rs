@@ -0,0 +1,103 @@
+use advent_of_code_2021_rust::automaton::{self, Automaton, Grid, Neighbourhood};
+
+const ACTUAL_INPUT: &str = include_str!("input.txt");
+
+pub fn parse_algorithm(algorithm: &str) -> Vec<bool> {
+    let algorithm = algorithm
+        .trim()
+        .chars()
+        .map(|c| c == '#')
+        .collect::<Vec<_>>();
+
+    if algorithm.len() != 512 {
+        panic!(
+            "Expected 512 entries in algorithm, found {}",
+            algorithm.len()
+        );
+    }
+
+    algorithm
+}
+
+// lit pixels are true
+pub fn parse_rows(image: &str) -> Vec<Vec<bool>> {
+    automaton::parse_rows(image, |c| c == '#')
+}
+
+// the image starts out on an unlit background
+pub fn parse_input(input: &str) -> (Vec<bool>, Grid<bool>) {
+    let (algorithm, image) = input.trim().split_once("\n\n").unwrap();
+    (
+        parse_algorithm(algorithm),
+        Grid::infinite(parse_rows(image), false),
+    )
+}
+
+fn solve(input: &str, total_iterations: usize) -> String {
+    let (algorithm, grid) = parse_input(input);
+
+    // the engine keeps track of the infinite background, so the image is
+    // always correct, even on iterations where the background is lit
+    let mut automaton =
+        Automaton::new(grid).with_phase(Neighbourhood::kernel_3x3(), |_, kernel| {
+            algorithm[kernel
+                .iter()
+                .fold(0, |index, lit| (index << 1) | *lit as usize)]
+        });
+
+    (0..total_iterations).for_each(|_| {
+        automaton.step();
+    });
+
+    if automaton.grid().background() == Some(true) {
+        "infinity".to_string()
+    } else {
+        automaton.grid().count(|lit| *lit).to_string()
+    }
+}
+
+fn p1(input: &str) -> String {
+    solve(input, 2)
+}
+
+fn p2(input: &str) -> String {
+    solve(input, 50)
+}
+
+pub fn solve_engine() {
+    println!("{}", p1(ACTUAL_INPUT));
+    println!("{}", p2(ACTUAL_INPUT));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = include_str!("example.txt");
+
+    #[test]
+    fn test_odd_iterations() {
+        assert_eq!(solve(ACTUAL_INPUT, 1), "infinity");
+        assert_eq!(solve(SAMPLE_INPUT, 1), "24");
+    }
+
+    #[test]
+    fn test_p1_sample() {
+        assert_eq!(p1(SAMPLE_INPUT), "35");
+    }
+
+    #[test]
+    fn test_p1_actual() {
+        assert_eq!(p1(ACTUAL_INPUT), "4917");
+    }
+
+    #[test]
+    fn test_p2_sample() {
+        assert_eq!(p2(SAMPLE_INPUT), "3351");
+    }
+
+    #[test]
+    fn test_p2_actual() {
+        assert_eq!(p2(ACTUAL_INPUT), "16389");
+    }
+}
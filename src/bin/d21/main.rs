@@ -6,15 +6,7 @@ fn parse_input(input: &str) -> Vec<i32> {
     input
         .trim()
         .lines()
-        .map(|line| {
-            line.split(' ')
-                .rev()
-                .next()
-                .unwrap()
-                .parse::<i32>()
-                .unwrap()
-                - 1
-        })
+        .map(|line| line.split(' ').next_back().unwrap().parse::<i32>().unwrap() - 1)
         .collect()
 }
 
@@ -0,0 +1,81 @@
+use super::{P2Command, P2CommandType};
+
+// all the boundaries of the given ranges, where each boundary is the
+// first coordinate of a new cell (hence max + 1), sorted and deduplicated
+fn get_cuts(ranges: impl Iterator<Item = (i64, i64)>) -> Vec<i64> {
+    let mut cuts = ranges
+        .flat_map(|(min, max)| [min, max + 1])
+        .collect::<Vec<_>>();
+    cuts.sort_unstable();
+    cuts.dedup();
+    cuts
+}
+
+fn get_cell_index(cuts: &[i64], coordinate: i64) -> usize {
+    cuts.binary_search(&coordinate).unwrap()
+}
+
+// a slab is a range of x-coordinates that no cuboid boundary passes
+// through, so every (y, z) cell inside the slab is either entirely on or
+// entirely off. The cells are only compressed against the commands that
+// actually pass through the slab, to keep the grid small.
+fn count_on_slab(commands: &[&P2Command]) -> i64 {
+    let ys = get_cuts(
+        commands
+            .iter()
+            .map(|command| (command.region.min.y, command.region.max.y)),
+    );
+    let zs = get_cuts(
+        commands
+            .iter()
+            .map(|command| (command.region.min.z, command.region.max.z)),
+    );
+
+    if ys.is_empty() {
+        return 0;
+    }
+
+    let width = zs.len() - 1;
+    let mut grid = vec![false; (ys.len() - 1) * width];
+
+    commands.iter().for_each(|command| {
+        let on = matches!(command.cmd_type, P2CommandType::On);
+
+        let y_start = get_cell_index(&ys, command.region.min.y);
+        let y_end = get_cell_index(&ys, command.region.max.y + 1);
+        let z_start = get_cell_index(&zs, command.region.min.z);
+        let z_end = get_cell_index(&zs, command.region.max.z + 1);
+
+        (y_start..y_end).for_each(|y| {
+            grid[(y * width + z_start)..(y * width + z_end)].fill(on);
+        });
+    });
+
+    grid.iter()
+        .enumerate()
+        .filter(|(_, on)| **on)
+        .map(|(i, _)| {
+            let (y, z) = (i / width, i % width);
+            (ys[y + 1] - ys[y]) * (zs[z + 1] - zs[z])
+        })
+        .sum()
+}
+
+pub fn count_on(commands: &[P2Command]) -> i64 {
+    let xs = get_cuts(
+        commands
+            .iter()
+            .map(|command| (command.region.min.x, command.region.max.x)),
+    );
+
+    xs.windows(2)
+        .map(|slab| {
+            let active_commands = commands
+                .iter()
+                .filter(|command| (command.region.min.x..=command.region.max.x).contains(&slab[0]))
+                .collect::<Vec<_>>();
+
+            (slab[1] - slab[0]) * count_on_slab(&active_commands)
+        })
+        .sum()
+}
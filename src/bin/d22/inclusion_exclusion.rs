@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use super::{Cuboid, P2Command, P2CommandType};
+
+// every cuboid that was ever added is kept together with a signed count.
+// When a new region comes in, each existing cuboid that overlaps with it
+// gets a "counter" cuboid (the overlap) with the opposite sign, so that the
+// overlap is no longer counted. If the region is turned on, it is added
+// with a +1 count afterwards.
+//
+// Identical cuboids are merged together (and dropped once their counts
+// cancel out), which keeps the list from growing on repeated overlaps.
+#[derive(Default)]
+pub struct SignedCuboids {
+    cuboids: HashMap<Cuboid, i64>,
+}
+
+impl SignedCuboids {
+    // returns the change in the number of cubes that are on
+    pub fn apply(&mut self, command: &P2Command) -> i64 {
+        let mut updates: HashMap<Cuboid, i64> = HashMap::new();
+
+        self.cuboids.iter().for_each(|(cuboid, count)| {
+            if let Some(overlap) = cuboid.intersection(&command.region) {
+                *updates.entry(overlap).or_default() -= count;
+            }
+        });
+
+        if let P2CommandType::On = command.cmd_type {
+            *updates.entry(command.region).or_default() += 1;
+        }
+
+        let delta = updates
+            .into_iter()
+            .map(|(cuboid, count)| {
+                *self.cuboids.entry(cuboid).or_default() += count;
+                cuboid.get_total_points() * count
+            })
+            .sum::<i64>();
+
+        self.cuboids.retain(|_, count| *count != 0);
+        delta
+    }
+}
+
+pub fn count_on(commands: &[P2Command]) -> i64 {
+    let mut signed_cuboids = SignedCuboids::default();
+
+    commands
+        .iter()
+        .map(|command| signed_cuboids.apply(command))
+        .sum()
+}
@@ -1,6 +1,13 @@
+mod compression;
+mod inclusion_exclusion;
+mod render;
+
 use std::collections::HashSet;
+use std::fmt;
 use std::ops::{Index, RangeInclusive};
+use std::time::Instant;
 
+use advent_of_code_2021_rust::rng::Rng;
 use regex::Regex;
 
 const ACTUAL_INPUT: &str = include_str!("input.txt");
@@ -98,7 +105,7 @@ fn p1(input: &str) -> String {
     on.len().to_string()
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Vec2 {
     x: i64,
     y: i64,
@@ -122,7 +129,7 @@ impl Index<usize> for Vec2 {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Rectangle {
     min: Vec2,
     max: Vec2,
@@ -145,9 +152,26 @@ impl Rectangle {
     fn intersects(&self, other: &Self) -> bool {
         !(0..2).all(|axis| self.max[axis] < other.min[axis] || other.max[axis] < self.min[axis])
     }
+
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        if self.intersects(other) {
+            Some(Rectangle::new(
+                Vec2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+                Vec2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn get_total_points(&self) -> i64 {
+        (0..2)
+            .map(|axis| self.max[axis] - self.min[axis] + 1)
+            .product()
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Vec3 {
     x: i64,
     y: i64,
@@ -208,7 +232,7 @@ impl Index<usize> for Vec3 {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Cuboid {
     min: Vec3,
     max: Vec3,
@@ -241,7 +265,7 @@ impl Cuboid {
     }
 
     fn cut_yz_plane(&self, plane: &Rectangle, x: i64) -> Vec<Self> {
-        if self.yz_plane().intersects(plane) && ((self.min.x + 1)..(self.max.x)).contains(&x) {
+        if self.yz_plane().intersects(plane) && ((self.min.x + 1)..=(self.max.x)).contains(&x) {
             vec![
                 Cuboid::new(self.min, self.max.new_x(x - 1)),
                 Cuboid::new(self.min.new_x(x), self.max),
@@ -252,7 +276,7 @@ impl Cuboid {
     }
 
     fn cut_xz_plane(&self, plane: &Rectangle, y: i64) -> Vec<Self> {
-        if self.xz_plane().intersects(plane) && ((self.min.y + 1)..(self.max.y)).contains(&y) {
+        if self.xz_plane().intersects(plane) && ((self.min.y + 1)..=(self.max.y)).contains(&y) {
             vec![
                 Cuboid::new(self.min, self.max.new_y(y - 1)),
                 Cuboid::new(self.min.new_y(y), self.max),
@@ -263,7 +287,7 @@ impl Cuboid {
     }
 
     fn cut_xy_plane(&self, plane: &Rectangle, z: i64) -> Vec<Self> {
-        if self.xy_plane().intersects(plane) && ((self.min.z + 1)..(self.max.z)).contains(&z) {
+        if self.xy_plane().intersects(plane) && ((self.min.z + 1)..=(self.max.z)).contains(&z) {
             vec![
                 Cuboid::new(self.min, self.max.new_z(z - 1)),
                 Cuboid::new(self.min.new_z(z), self.max),
@@ -273,23 +297,61 @@ impl Cuboid {
         }
     }
 
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Vec3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Vec3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+
+        if (0..3).all(|axis| min[axis] <= max[axis]) {
+            Some(Cuboid::new(min, max))
+        } else {
+            None
+        }
+    }
+
     fn contains(&self, other: &Self) -> bool {
         (0..3).all(|axis| other.min[axis] >= self.min[axis] && other.max[axis] <= self.max[axis])
     }
 
+    // a cut at coordinate n splits the cuboid into [min, n - 1] and [n, max],
+    // so the far side of `other` is cut at max + 1
     fn subtract(&self, other: &Self) -> Vec<Self> {
         vec![*self]
             .into_iter()
             .flat_map(|part| part.cut_yz_plane(&other.yz_plane(), other.min.x))
-            .flat_map(|part| part.cut_yz_plane(&other.yz_plane(), other.max.x))
+            .flat_map(|part| part.cut_yz_plane(&other.yz_plane(), other.max.x + 1))
             .flat_map(|part| part.cut_xz_plane(&other.xz_plane(), other.min.y))
-            .flat_map(|part| part.cut_xz_plane(&other.xz_plane(), other.max.y))
+            .flat_map(|part| part.cut_xz_plane(&other.xz_plane(), other.max.y + 1))
             .flat_map(|part| part.cut_xy_plane(&other.xy_plane(), other.min.z))
-            .flat_map(|part| part.cut_xy_plane(&other.xy_plane(), other.max.z))
+            .flat_map(|part| part.cut_xy_plane(&other.xy_plane(), other.max.z + 1))
             .filter(|part| !other.contains(part))
             .collect()
     }
 
+    // parses the "x=a..b,y=c..d,z=e..f" format used by the puzzle input
+    fn parse_region(region: &str) -> Self {
+        let re = Regex::new(
+            r"^x=(-*[\d]+)\.\.(-*[\d]+),y=(-*[\d]+)\.\.(-*[\d]+),z=(-*[\d]+)\.\.(-*[\d]+)$",
+        )
+        .unwrap();
+
+        let cap = re
+            .captures(region.trim())
+            .unwrap_or_else(|| panic!("Invalid region {}", region));
+
+        Cuboid::new(
+            Vec3::new_from_str(&cap[1], &cap[3], &cap[5]),
+            Vec3::new_from_str(&cap[2], &cap[4], &cap[6]),
+        )
+    }
+
     fn get_total_points(&self) -> i64 {
         (0..3)
             .map(|axis| self.max[axis] - self.min[axis] + 1)
@@ -297,6 +359,16 @@ impl Cuboid {
     }
 }
 
+impl fmt::Display for Cuboid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x={}..{},y={}..{},z={}..{}",
+            self.min.x, self.max.x, self.min.y, self.max.y, self.min.z, self.max.z
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum P2CommandType {
     Off,
@@ -311,62 +383,385 @@ struct P2Command {
 
 impl P2Command {
     fn parse_input(input: &str) -> Vec<Self> {
-        let re = Regex::new(
-            r"(on|off) x=(-*[\d]+)\.\.(-*[\d]+),y=(-*[\d]+)\.\.(-*[\d]+),z=(-*[\d]+)\.\.(-*[\d]+)",
-        )
-        .unwrap();
-
         input
             .trim()
             .lines()
             .map(|line| {
-                let cap = re.captures(line).unwrap();
+                let (cmd_type, region) = line.trim().split_once(' ').unwrap();
 
                 P2Command {
-                    cmd_type: if &cap[1] == "on" {
-                        P2CommandType::On
-                    } else {
-                        P2CommandType::Off
+                    cmd_type: match cmd_type {
+                        "on" => P2CommandType::On,
+                        "off" => P2CommandType::Off,
+                        _ => panic!("Unknown command {}", cmd_type),
                     },
-                    region: Cuboid::new(
-                        Vec3::new_from_str(&cap[2], &cap[4], &cap[6]),
-                        Vec3::new_from_str(&cap[3], &cap[5], &cap[7]),
-                    ),
+                    region: Cuboid::parse_region(region),
                 }
             })
             .collect()
     }
 }
 
-fn p2(input: &str) -> String {
-    P2Command::parse_input(input)
+impl fmt::Display for P2Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cmd_type {
+            P2CommandType::On => write!(f, "on {}", self.region),
+            P2CommandType::Off => write!(f, "off {}", self.region),
+        }
+    }
+}
+
+// the original approach: every lit cuboid is split against each new
+// region, so that the lit cuboids never overlap with each other
+fn apply_subtract(lit_cuboids: Vec<Cuboid>, command: &P2Command) -> Vec<Cuboid> {
+    let mut new_cuboids = lit_cuboids
         .into_iter()
-        .fold(vec![], |acc: Vec<Cuboid>, command| {
-            let mut new_cuboids = acc
-                .into_iter()
-                .flat_map(|cuboid| cuboid.subtract(&command.region))
-                .collect::<Vec<_>>();
+        .flat_map(|cuboid| cuboid.subtract(&command.region))
+        .collect::<Vec<_>>();
 
-            if let P2CommandType::On = command.cmd_type {
-                new_cuboids.push(command.region);
-            }
+    if let P2CommandType::On = command.cmd_type {
+        new_cuboids.push(command.region);
+    }
 
-            new_cuboids
-        })
+    new_cuboids
+}
+
+fn count_on_subtract(commands: &[P2Command]) -> i64 {
+    commands
+        .iter()
+        .fold(vec![], apply_subtract)
         .into_iter()
         .fold(0, |acc, cuboid| acc + cuboid.get_total_points())
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Strategy {
+    Subtract,
+    InclusionExclusion,
+    Compression,
+}
+
+impl Strategy {
+    const ALL: [Strategy; 3] = [
+        Strategy::Subtract,
+        Strategy::InclusionExclusion,
+        Strategy::Compression,
+    ];
+
+    fn count_on(&self, commands: &[P2Command]) -> i64 {
+        match self {
+            Strategy::Subtract => count_on_subtract(commands),
+            Strategy::InclusionExclusion => inclusion_exclusion::count_on(commands),
+            Strategy::Compression => compression::count_on(commands),
+        }
+    }
+}
+
+// crops every command to the given region, dropping those that fall
+// entirely outside of it
+fn restrict_to_region(commands: &[P2Command], region: &Cuboid) -> Vec<P2Command> {
+    commands
+        .iter()
+        .filter_map(|command| {
+            command.region.intersection(region).map(|region| P2Command {
+                cmd_type: command.cmd_type,
+                region,
+            })
+        })
+        .collect()
+}
+
+fn get_p1_region() -> Cuboid {
+    Cuboid::new(Vec3::new(-50, -50, -50), Vec3::new(50, 50, 50))
+}
+
+// how many cubes are on within the region after the first `steps`
+// reboot steps (steps beyond the end of the list are ignored)
+fn count_on_in_region(commands: &[P2Command], region: &Cuboid, steps: usize) -> i64 {
+    inclusion_exclusion::count_on(&restrict_to_region(
+        &commands[..steps.min(commands.len())],
+        region,
+    ))
+}
+
+// the change in the number of cubes that are on within the region,
+// caused by each reboot step
+fn get_step_deltas(commands: &[P2Command], region: &Cuboid) -> Vec<i64> {
+    let mut signed_cuboids = inclusion_exclusion::SignedCuboids::default();
+
+    commands
+        .iter()
+        .map(|command| {
+            command
+                .region
+                .intersection(region)
+                .map(|region| {
+                    signed_cuboids.apply(&P2Command {
+                        cmd_type: command.cmd_type,
+                        region,
+                    })
+                })
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+fn p1_with_strategy(input: &str, strategy: Strategy) -> String {
+    strategy
+        .count_on(&restrict_to_region(
+            &P2Command::parse_input(input),
+            &get_p1_region(),
+        ))
+        .to_string()
+}
+
+fn p2_with_strategy(input: &str, strategy: Strategy) -> String {
+    strategy
+        .count_on(&P2Command::parse_input(input))
         .to_string()
 }
 
+fn p2(input: &str) -> String {
+    p2_with_strategy(input, Strategy::InclusionExclusion)
+}
+
+// large cuboids that all overlap each other near the origin, which is the
+// worst case for the subtract approach (every new region cuts through
+// most of the existing cuboids)
+fn generate_stress_input(seed: u64, total_commands: usize, extent: i64) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..total_commands)
+        .map(|_| {
+            let command_type = if rng.next_u64().is_multiple_of(3) {
+                "off"
+            } else {
+                "on"
+            };
+            let ranges = (0..3)
+                .map(|_| {
+                    let min = rng.next_in_range(-extent, extent / 2);
+                    let max = rng.next_in_range(min, extent);
+                    (min, max)
+                })
+                .collect::<Vec<_>>();
+
+            format!(
+                "{} x={}..{},y={}..{},z={}..{}",
+                command_type,
+                ranges[0].0,
+                ranges[0].1,
+                ranges[1].0,
+                ranges[1].1,
+                ranges[2].0,
+                ranges[2].1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn benchmark(name: &str, input: &str, solve: fn(&str, Strategy) -> String) {
+    println!("{}:", name);
+
+    Strategy::ALL.iter().for_each(|strategy| {
+        let start = Instant::now();
+        let answer = solve(input, *strategy);
+        println!("    {:?}: {} ({:.3?})", strategy, answer, start.elapsed());
+    });
+}
+
 fn main() {
     println!("{}", p1(ACTUAL_INPUT));
     println!("{}", p2(ACTUAL_INPUT));
+
+    let args = std::env::args().collect::<Vec<_>>();
+
+    match args.get(1).map(String::as_str) {
+        // cargo run --release --bin d22 -- bench
+        Some("bench") => {
+            println!();
+            benchmark("Actual input (p1)", ACTUAL_INPUT, p1_with_strategy);
+            benchmark("Actual input (p2)", ACTUAL_INPUT, p2_with_strategy);
+            benchmark(
+                "Stress input (200 commands)",
+                &generate_stress_input(2021, 200, 10000),
+                p2_with_strategy,
+            );
+            benchmark(
+                "Stress input (400 commands)",
+                &generate_stress_input(2021, 400, 10000),
+                p2_with_strategy,
+            );
+        }
+        // cargo run --release --bin d22 -- query x=-50..50,y=-50..50,z=-50..50 [steps]
+        Some("query") => {
+            let region = Cuboid::parse_region(args.get(2).expect("Missing region"));
+            let commands = P2Command::parse_input(ACTUAL_INPUT);
+            let steps = args
+                .get(3)
+                .map(|steps| steps.parse::<usize>().unwrap())
+                .unwrap_or(commands.len());
+
+            println!();
+            println!(
+                "On within {} after {} steps: {}",
+                region,
+                steps,
+                count_on_in_region(&commands, &region, steps)
+            );
+
+            let mut total = 0;
+            commands
+                .iter()
+                .zip(get_step_deltas(&commands, &region))
+                .take(steps)
+                .enumerate()
+                .for_each(|(i, (command, delta))| {
+                    total += delta;
+                    println!(
+                        "Step {}: {:+} (total {}) [{}]",
+                        i + 1,
+                        delta,
+                        total,
+                        command
+                    );
+                });
+        }
+        // cargo run --release --bin d22 -- slice z 0 [steps] [size]
+        Some("slice") => {
+            let commands = P2Command::parse_input(ACTUAL_INPUT);
+            let axis = render::Axis::parse(args.get(2).expect("Missing axis"));
+            let coordinate = args.get(3).expect("Missing coordinate").parse().unwrap();
+            let steps = args
+                .get(4)
+                .map(|steps| steps.parse::<usize>().unwrap())
+                .unwrap_or(commands.len());
+            let size = args.get(5).map(|size| size.parse().unwrap()).unwrap_or(80);
+
+            let viewport = render::Viewport::fit(&commands, axis, size);
+            println!();
+            println!(
+                "{}",
+                viewport.render_ascii(&render::get_slice(&commands, axis, coordinate, steps))
+            );
+        }
+        // cargo run --release --bin d22 -- frames z 0 <directory> [size]
+        Some("frames") => {
+            let commands = P2Command::parse_input(ACTUAL_INPUT);
+            let axis = render::Axis::parse(args.get(2).expect("Missing axis"));
+            let coordinate = args.get(3).expect("Missing coordinate").parse().unwrap();
+            let directory = args.get(4).expect("Missing output directory");
+            let size = args.get(5).map(|size| size.parse().unwrap()).unwrap_or(400);
+
+            render::write_ppm_frames(
+                &render::get_slice_frames(&commands, axis, coordinate),
+                &render::Viewport::fit(&commands, axis, size),
+                std::path::Path::new(directory),
+            )
+            .unwrap();
+        }
+        _ => {}
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cuboid_intersection() {
+        let a = Cuboid::new(Vec3::new(0, 0, 0), Vec3::new(10, 10, 10));
+        let b = Cuboid::new(Vec3::new(5, -5, 10), Vec3::new(15, 5, 20));
+        let c = Cuboid::new(Vec3::new(11, 0, 0), Vec3::new(12, 10, 10));
+
+        assert_eq!(
+            a.intersection(&b),
+            Some(Cuboid::new(Vec3::new(5, 0, 10), Vec3::new(10, 5, 10)))
+        );
+        assert_eq!(a.intersection(&c), None);
+    }
+
+    #[test]
+    fn test_cuboid_subtract() {
+        let a = Cuboid::new(Vec3::new(0, 0, 0), Vec3::new(2, 2, 2));
+        let b = Cuboid::new(Vec3::new(1, 1, 1), Vec3::new(1, 1, 1));
+
+        let parts = a.subtract(&b);
+        assert_eq!(parts.iter().map(Cuboid::get_total_points).sum::<i64>(), 26);
+        assert!(parts.iter().all(|part| part.intersection(&b).is_none()));
+    }
+
+    #[test]
+    fn test_parse_region() {
+        let region = Cuboid::parse_region("x=-54112..-39298,y=-85059..-49293,z=-27449..7877");
+
+        assert_eq!(
+            region,
+            Cuboid::new(
+                Vec3::new(-54112, -85059, -27449),
+                Vec3::new(-39298, -49293, 7877)
+            )
+        );
+        assert_eq!(
+            region.to_string(),
+            "x=-54112..-39298,y=-85059..-49293,z=-27449..7877"
+        );
+    }
+
+    #[test]
+    fn test_count_on_in_region() {
+        let commands = P2Command::parse_input(
+            r"
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10
+",
+        );
+        let everything = Cuboid::parse_region("x=0..20,y=0..20,z=0..20");
+
+        assert_eq!(count_on_in_region(&commands, &everything, 0), 0);
+        assert_eq!(count_on_in_region(&commands, &everything, 1), 27);
+        assert_eq!(count_on_in_region(&commands, &everything, 2), 46);
+        assert_eq!(count_on_in_region(&commands, &everything, 3), 38);
+        assert_eq!(count_on_in_region(&commands, &everything, 4), 39);
+        assert_eq!(count_on_in_region(&commands, &everything, 100), 39);
+
+        let corner = Cuboid::parse_region("x=13..13,y=13..13,z=13..13");
+        assert_eq!(count_on_in_region(&commands, &corner, 1), 0);
+        assert_eq!(count_on_in_region(&commands, &corner, 4), 1);
+
+        assert_eq!(get_step_deltas(&commands, &everything), vec![27, 19, -8, 1]);
+        assert_eq!(get_step_deltas(&commands, &corner), vec![0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_strategies_agree_sample() {
+        let sample = r"
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10
+";
+
+        Strategy::ALL.iter().for_each(|strategy| {
+            assert_eq!(p1_with_strategy(sample, *strategy), "39");
+            assert_eq!(p2_with_strategy(sample, *strategy), "39");
+        });
+    }
+
+    #[test]
+    fn test_strategies_agree_stress() {
+        let input = generate_stress_input(2021, 30, 100);
+        let expected = p2_with_strategy(&input, Strategy::Subtract);
+
+        Strategy::ALL.iter().for_each(|strategy| {
+            assert_eq!(p1_with_strategy(&input, *strategy), p1(&input));
+            assert_eq!(p2_with_strategy(&input, *strategy), expected);
+        });
+    }
+
     #[test]
     fn test_p1_sample() {
         assert_eq!(
@@ -410,43 +805,111 @@ on x=967..23432,y=45373..81175,z=27513..53682
     #[test]
     fn test_p1_actual() {
         assert_eq!(p1(ACTUAL_INPUT), "581108");
+
+        Strategy::ALL.iter().for_each(|strategy| {
+            assert_eq!(p1_with_strategy(ACTUAL_INPUT, *strategy), "581108");
+        });
+
+        let commands = P2Command::parse_input(ACTUAL_INPUT);
+        assert_eq!(
+            count_on_in_region(&commands, &get_p1_region(), commands.len()),
+            581108
+        );
+        assert_eq!(
+            get_step_deltas(&commands, &get_p1_region())
+                .into_iter()
+                .sum::<i64>(),
+            581108
+        );
     }
 
     #[test]
-    #[ignore = "not yet implemented"]
     fn test_p2_sample() {
+        let input = r"
+on x=-5..47,y=-31..22,z=-19..33
+on x=-44..5,y=-27..21,z=-14..35
+on x=-49..-1,y=-11..42,z=-10..38
+on x=-20..34,y=-40..6,z=-44..1
+off x=26..39,y=40..50,z=-2..11
+on x=-41..5,y=-41..6,z=-36..8
+off x=-43..-33,y=-45..-28,z=7..25
+on x=-33..15,y=-32..19,z=-34..11
+off x=35..47,y=-46..-34,z=-11..5
+on x=-14..36,y=-6..44,z=-16..29
+on x=-57795..-6158,y=29564..72030,z=20435..90618
+on x=36731..105352,y=-21140..28532,z=16094..90401
+on x=30999..107136,y=-53464..15513,z=8553..71215
+on x=13528..83982,y=-99403..-27377,z=-24141..23996
+on x=-72682..-12347,y=18159..111354,z=7391..80950
+on x=-1060..80757,y=-65301..-20884,z=-103788..-16709
+on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856
+on x=-52752..22273,y=-49450..9096,z=54442..119054
+on x=-29982..40483,y=-108474..-28371,z=-24328..38471
+on x=-4958..62750,y=40422..118853,z=-7672..65583
+on x=55694..108686,y=-43367..46958,z=-26781..48729
+on x=-98497..-18186,y=-63569..3412,z=1232..88485
+on x=-726..56291,y=-62629..13224,z=18033..85226
+on x=-110886..-34664,y=-81338..-8658,z=8914..63723
+on x=-55829..24974,y=-16897..54165,z=-121762..-28058
+on x=-65152..-11147,y=22489..91432,z=-58782..1780
+on x=-120100..-32970,y=-46592..27473,z=-11695..61039
+on x=-18631..37533,y=-124565..-50804,z=-35667..28308
+on x=-57817..18248,y=49321..117703,z=5745..55881
+on x=14781..98692,y=-1341..70827,z=15753..70151
+on x=-34419..55919,y=-19626..40991,z=39015..114138
+on x=-60785..11593,y=-56135..2999,z=-95368..-26915
+on x=-32178..58085,y=17647..101866,z=-91405..-8878
+on x=-53655..12091,y=50097..105568,z=-75335..-4862
+on x=-111166..-40997,y=-71714..2688,z=5609..50954
+on x=-16602..70118,y=-98693..-44401,z=5197..76897
+on x=16383..101554,y=4615..83635,z=-44907..18747
+off x=-95822..-15171,y=-19987..48940,z=10804..104439
+on x=-89813..-14614,y=16069..88491,z=-3297..45228
+on x=41075..99376,y=-20427..49978,z=-52012..13762
+on x=-21330..50085,y=-17944..62733,z=-112280..-30197
+on x=-16478..35915,y=36008..118594,z=-7885..47086
+off x=-98156..-27851,y=-49952..43171,z=-99005..-8456
+off x=2032..69770,y=-71013..4824,z=7471..94418
+on x=43670..120875,y=-42068..12382,z=-24787..38892
+off x=37514..111226,y=-45862..25743,z=-16714..54663
+off x=25699..97951,y=-30668..59918,z=-15349..69697
+off x=-44271..17935,y=-9516..60759,z=49131..112598
+on x=-61695..-5813,y=40978..94975,z=8655..80240
+off x=-101086..-9439,y=-7088..67543,z=33935..83858
+off x=18020..114017,y=-48931..32606,z=21474..89843
+off x=-77139..10506,y=-89994..-18797,z=-80..59318
+off x=8476..79288,y=-75520..11602,z=-96624..-24783
+on x=-47488..-1262,y=24338..100707,z=16292..72967
+off x=-84341..13987,y=2429..92914,z=-90671..-1318
+off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
+off x=-27365..46395,y=31009..98017,z=15428..76570
+off x=-70369..-16548,y=22648..78696,z=-1892..86821
+on x=-53470..21291,y=-120233..-33476,z=-44150..38147
+off x=-93533..-4276,y=-16170..68771,z=-104985..-24507
+";
+
+        assert_eq!(p2(input), "2758514936282235");
+
+        Strategy::ALL.iter().for_each(|strategy| {
+            assert_eq!(p2_with_strategy(input, *strategy), "2758514936282235");
+        });
+    }
+
+    #[test]
+    fn test_p2_actual() {
+        assert_eq!(p2(ACTUAL_INPUT), "1325473814582641");
         assert_eq!(
-            p2(r"
-on x=-20..26,y=-36..17,z=-47..7
-on x=-20..33,y=-21..23,z=-26..28
-on x=-22..28,y=-29..23,z=-38..16
-on x=-46..7,y=-6..46,z=-50..-1
-on x=-49..1,y=-3..46,z=-24..28
-on x=2..47,y=-22..22,z=-23..27
-on x=-27..23,y=-28..26,z=-21..29
-on x=-39..5,y=-6..47,z=-3..44
-on x=-30..21,y=-8..43,z=-13..34
-on x=-22..26,y=-27..20,z=-29..19
-off x=-48..-32,y=26..41,z=-47..-37
-on x=-12..35,y=6..50,z=-50..-2
-off x=-48..-32,y=-32..-16,z=-15..-5
-on x=-18..26,y=-33..15,z=-7..46
-off x=-40..-22,y=-38..-28,z=23..41
-on x=-16..35,y=-41..10,z=-47..6
-off x=-32..-23,y=11..30,z=-14..3
-on x=-49..-5,y=-3..45,z=-29..18
-off x=18..30,y=-20..-8,z=-3..13
-on x=-41..9,y=-7..43,z=-33..15
-on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
-on x=967..23432,y=45373..81175,z=27513..53682
-"),
-            "2758514936282235"
+            p2_with_strategy(ACTUAL_INPUT, Strategy::Compression),
+            "1325473814582641"
         );
     }
 
     #[test]
-    #[ignore = "not yet implemented"]
-    fn test_p2_actual() {
-        assert_eq!(p2(ACTUAL_INPUT), "");
+    #[ignore = "expensive to run"]
+    fn test_p2_actual_subtract() {
+        assert_eq!(
+            p2_with_strategy(ACTUAL_INPUT, Strategy::Subtract),
+            "1325473814582641"
+        );
     }
 }
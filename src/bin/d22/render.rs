@@ -0,0 +1,285 @@
+use std::{fs, io, path::Path};
+
+use advent_of_code_2021_rust::ppm;
+
+use super::{apply_subtract, Cuboid, P2Command, Rectangle, Vec2, Vec3};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub fn parse(axis: &str) -> Self {
+        match axis {
+            "x" => Axis::X,
+            "y" => Axis::Y,
+            "z" => Axis::Z,
+            _ => panic!("Unknown axis {}", axis),
+        }
+    }
+
+    // the plane perpendicular to the axis
+    fn project(&self, cuboid: &Cuboid) -> Rectangle {
+        match self {
+            Axis::X => cuboid.yz_plane(),
+            Axis::Y => cuboid.xz_plane(),
+            Axis::Z => cuboid.xy_plane(),
+        }
+    }
+
+    fn with_coordinate(&self, vec: &Vec3, coordinate: i64) -> Vec3 {
+        match self {
+            Axis::X => vec.new_x(coordinate),
+            Axis::Y => vec.new_y(coordinate),
+            Axis::Z => vec.new_z(coordinate),
+        }
+    }
+}
+
+fn get_bounds(commands: &[P2Command]) -> Cuboid {
+    let min = (0..3)
+        .map(|axis| commands.iter().map(|c| c.region.min[axis]).min().unwrap())
+        .collect::<Vec<_>>();
+    let max = (0..3)
+        .map(|axis| commands.iter().map(|c| c.region.max[axis]).max().unwrap())
+        .collect::<Vec<_>>();
+
+    Cuboid::new(
+        Vec3::new(min[0], min[1], min[2]),
+        Vec3::new(max[0], max[1], max[2]),
+    )
+}
+
+// the lit cross-section after every reboot step (frames[i] is the state
+// after step i + 1). The commands are cropped to the one cube thick slice
+// first, so that the subtract approach stays cheap even on the actual input
+pub fn get_slice_frames(
+    commands: &[P2Command],
+    axis: Axis,
+    coordinate: i64,
+) -> Vec<Vec<Rectangle>> {
+    if commands.is_empty() {
+        return vec![];
+    }
+
+    let bounds = get_bounds(commands);
+    let slice_region = Cuboid::new(
+        axis.with_coordinate(&bounds.min, coordinate),
+        axis.with_coordinate(&bounds.max, coordinate),
+    );
+
+    commands
+        .iter()
+        .scan(vec![], |lit_cuboids: &mut Vec<Cuboid>, command| {
+            if let Some(region) = command.region.intersection(&slice_region) {
+                *lit_cuboids = apply_subtract(
+                    std::mem::take(lit_cuboids),
+                    &P2Command {
+                        cmd_type: command.cmd_type,
+                        region,
+                    },
+                );
+            }
+
+            Some(
+                lit_cuboids
+                    .iter()
+                    .map(|cuboid| axis.project(cuboid))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+// the lit cross-section after the first `steps` reboot steps
+pub fn get_slice(
+    commands: &[P2Command],
+    axis: Axis,
+    coordinate: i64,
+    steps: usize,
+) -> Vec<Rectangle> {
+    get_slice_frames(&commands[..steps.min(commands.len())], axis, coordinate)
+        .pop()
+        .unwrap_or_default()
+}
+
+// maps the cubes of a plane to pixels, where every pixel covers a square
+// of `scale` x `scale` cubes
+pub struct Viewport {
+    min: Vec2,
+    scale: i64,
+    width: usize,
+    height: usize,
+}
+
+impl Viewport {
+    // fits the whole reactor (as seen along the axis) into at most
+    // `max_size` pixels on the longer side
+    pub fn fit(commands: &[P2Command], axis: Axis, max_size: usize) -> Self {
+        Self::new(&axis.project(&get_bounds(commands)), max_size)
+    }
+
+    // fits the given part of the plane into at most `max_size` pixels on
+    // the longer side (one pixel per cube, if it is small enough)
+    pub fn new(bounds: &Rectangle, max_size: usize) -> Self {
+        let spans = (0..2)
+            .map(|i| bounds.max[i] - bounds.min[i] + 1)
+            .collect::<Vec<_>>();
+        let max_size = max_size.max(1) as i64;
+        let scale = (spans[0].max(spans[1]) + max_size - 1) / max_size;
+
+        Self {
+            min: bounds.min,
+            scale,
+            width: ((spans[0] + scale - 1) / scale) as usize,
+            height: ((spans[1] + scale - 1) / scale) as usize,
+        }
+    }
+
+    fn get_pixel_region(&self, col: usize, row: usize) -> Rectangle {
+        let min = Vec2::new(
+            self.min.x + col as i64 * self.scale,
+            self.min.y + row as i64 * self.scale,
+        );
+        Rectangle::new(
+            min,
+            Vec2::new(min.x + self.scale - 1, min.y + self.scale - 1),
+        )
+    }
+
+    // the fraction of each pixel that is lit, row by row
+    fn get_coverage(&self, slice: &[Rectangle]) -> Vec<f64> {
+        let mut lit = vec![0; self.width * self.height];
+        let bounds = Rectangle::new(
+            self.min,
+            Vec2::new(
+                self.min.x + self.width as i64 * self.scale - 1,
+                self.min.y + self.height as i64 * self.scale - 1,
+            ),
+        );
+
+        // the rectangles never overlap, so their areas can simply be added up
+        slice
+            .iter()
+            .filter_map(|rectangle| rectangle.intersection(&bounds))
+            .for_each(|rectangle| {
+                let cols = ((rectangle.min.x - self.min.x) / self.scale) as usize
+                    ..=((rectangle.max.x - self.min.x) / self.scale) as usize;
+                let rows = ((rectangle.min.y - self.min.y) / self.scale) as usize
+                    ..=((rectangle.max.y - self.min.y) / self.scale) as usize;
+
+                rows.for_each(|row| {
+                    cols.clone().for_each(|col| {
+                        if let Some(overlap) =
+                            rectangle.intersection(&self.get_pixel_region(col, row))
+                        {
+                            lit[row * self.width + col] += overlap.get_total_points();
+                        }
+                    });
+                });
+            });
+
+        let pixel_area = (self.scale * self.scale) as f64;
+        lit.into_iter().map(|lit| lit as f64 / pixel_area).collect()
+    }
+
+    // '#' for pixels that are at least half lit
+    pub fn render_ascii(&self, slice: &[Rectangle]) -> String {
+        self.get_coverage(slice)
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|coverage| if *coverage >= 0.5 { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // binary PPM (P6), where partially lit pixels are shaded
+    pub fn render_ppm(&self, slice: &[Rectangle]) -> Vec<u8> {
+        ppm::encode(
+            self.width,
+            self.height,
+            self.get_coverage(slice).into_iter().map(|coverage| {
+                [255.0, 176.0, 0.0].map(|channel| (channel * coverage).round() as u8)
+            }),
+        )
+    }
+}
+
+// one PPM per reboot step, named step_001.ppm, step_002.ppm, etc
+pub fn write_ppm_frames(
+    frames: &[Vec<Rectangle>],
+    viewport: &Viewport,
+    directory: &Path,
+) -> io::Result<()> {
+    fs::create_dir_all(directory)?;
+
+    frames.iter().enumerate().try_for_each(|(i, frame)| {
+        fs::write(
+            directory.join(format!("step_{:03}.ppm", i + 1)),
+            viewport.render_ppm(frame),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = r"
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10
+";
+
+    #[test]
+    fn test_get_slice_frames() {
+        let commands = P2Command::parse_input(SAMPLE_INPUT);
+        let frames = get_slice_frames(&commands, Axis::Z, 10);
+
+        assert_eq!(frames.len(), 4);
+        assert_eq!(
+            frames
+                .iter()
+                .map(|frame| frame.iter().map(Rectangle::get_total_points).sum::<i64>())
+                .collect::<Vec<_>>(),
+            vec![9, 9, 5, 6]
+        );
+        assert!(get_slice(&commands, Axis::X, 20, 4).is_empty());
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let commands = P2Command::parse_input(SAMPLE_INPUT);
+        let viewport = Viewport::new(&Rectangle::new(Vec2::new(9, 9), Vec2::new(13, 13)), 10);
+
+        assert_eq!(
+            viewport.render_ascii(&get_slice(&commands, Axis::Z, 10, 4)),
+            [".....", ".#.#.", "...#.", ".###.", "....."].join("\n")
+        );
+        assert_eq!(
+            viewport.render_ascii(&get_slice(&commands, Axis::X, 13, 4)),
+            [".....", ".....", "..###", "..###", "..###"].join("\n")
+        );
+    }
+
+    #[test]
+    fn test_render_ppm() {
+        let commands = P2Command::parse_input(SAMPLE_INPUT);
+        let viewport = Viewport::new(&Rectangle::new(Vec2::new(10, 10), Vec2::new(13, 13)), 2);
+        let ppm = viewport.render_ppm(&get_slice(&commands, Axis::Z, 10, 4));
+
+        let header = "P6\n2 2\n255\n".as_bytes();
+        assert_eq!(&ppm[..header.len()], header);
+
+        // top left pixel covers (10..11, 10..11), of which 1 cube is lit
+        assert_eq!(&ppm[header.len()..(header.len() + 3)], &[64, 44, 0]);
+        assert_eq!(ppm.len(), header.len() + 2 * 2 * 3);
+    }
+}
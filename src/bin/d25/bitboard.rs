@@ -0,0 +1,320 @@
+use advent_of_code_2021_rust::automaton::Grid;
+
+use crate::engine::{parse_grid, Cell};
+
+const ACTUAL_INPUT: &str = include_str!("input.txt");
+
+// one bit per column, packed into u64 words (bit x % 64 of word x / 64)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Row {
+    words: Vec<u64>,
+}
+
+impl Row {
+    fn new(width: usize) -> Self {
+        Self {
+            words: vec![0; width.div_ceil(64)],
+        }
+    }
+
+    fn get(&self, x: usize) -> bool {
+        (self.words[x / 64] >> (x % 64)) & 1 == 1
+    }
+
+    fn set(&mut self, x: usize) {
+        self.words[x / 64] |= 1 << (x % 64);
+    }
+
+    fn zip_with(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(a, b)| op(*a, *b))
+                .collect(),
+        }
+    }
+
+    fn or(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    fn and_not(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    fn count_ones(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+
+    // bits past the width are never set, so they don't leak into the
+    // wraparound
+    fn clear_unused_bits(&mut self, width: usize) {
+        if !width.is_multiple_of(64) {
+            *self.words.last_mut().unwrap() &= (1 << (width % 64)) - 1;
+        }
+    }
+
+    // every bit moves from x to x + 1, and the last column wraps to 0
+    fn rotate_right(&self, width: usize) -> Self {
+        let mut carry = 0;
+        let mut result = Self {
+            words: self
+                .words
+                .iter()
+                .map(|word| {
+                    let shifted = (word << 1) | carry;
+                    carry = word >> 63;
+                    shifted
+                })
+                .collect(),
+        };
+
+        result.clear_unused_bits(width);
+        if self.get(width - 1) {
+            result.set(0);
+        }
+        result
+    }
+
+    // every bit moves from x to x - 1, and column 0 wraps to the last column
+    fn rotate_left(&self, width: usize) -> Self {
+        let mut result = Self {
+            words: (0..self.words.len())
+                .map(|i| {
+                    let next = self.words.get(i + 1).copied().unwrap_or(0);
+                    (self.words[i] >> 1) | (next << 63)
+                })
+                .collect(),
+        };
+
+        if self.get(0) {
+            result.set(width - 1);
+        }
+        result
+    }
+}
+
+fn get_occupied(right: &[Row], down: &[Row]) -> Vec<Row> {
+    right
+        .iter()
+        .zip(down.iter())
+        .map(|(right, down)| right.or(down))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SeaMap {
+    width: usize,
+    right: Vec<Row>,
+    down: Vec<Row>,
+}
+
+impl SeaMap {
+    // packs the engine's grid, one row of bits per herd
+    fn from_grid(grid: &Grid<Cell>) -> SeaMap {
+        let width = grid.width();
+
+        let mut right = vec![Row::new(width); grid.height()];
+        let mut down = vec![Row::new(width); grid.height()];
+
+        grid.iter().for_each(|((x, y), cell)| match cell {
+            Cell::Right => right[y as usize].set(x as usize),
+            Cell::Down => down[y as usize].set(x as usize),
+            Cell::Empty => {}
+        });
+
+        SeaMap { width, right, down }
+    }
+
+    fn parse_input(input: &str) -> SeaMap {
+        Self::from_grid(&parse_grid(input))
+    }
+
+    fn simulate_one_step(&self) -> (SeaMap, u32) {
+        let width = self.width;
+        let height = self.right.len();
+
+        // east-facing herd: moves if the cell on its right is free
+        let occupied = get_occupied(&self.right, &self.down);
+        let right = self
+            .right
+            .iter()
+            .zip(occupied.iter())
+            .map(|(right, occupied)| {
+                let movers = right.and_not(&occupied.rotate_left(width));
+                (
+                    right.and_not(&movers).or(&movers.rotate_right(width)),
+                    movers.count_ones(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let right_moved = right.iter().map(|(_, moved)| moved).sum::<u32>();
+        let right = right.into_iter().map(|(row, _)| row).collect::<Vec<_>>();
+
+        // south-facing herd: moves if the cell below (after the east-facing
+        // herd has moved) is free, where the last row wraps to the first
+        let occupied = get_occupied(&right, &self.down);
+        let movers = (0..height)
+            .map(|y| self.down[y].and_not(&occupied[(y + 1) % height]))
+            .collect::<Vec<_>>();
+        let down = (0..height)
+            .map(|y| {
+                self.down[y]
+                    .and_not(&movers[y])
+                    .or(&movers[(y + height - 1) % height])
+            })
+            .collect::<Vec<_>>();
+        let down_moved = movers.iter().map(Row::count_ones).sum::<u32>();
+
+        (SeaMap { width, right, down }, right_moved + down_moved)
+    }
+}
+
+fn p1(input: &str) -> String {
+    let mut sea_map = SeaMap::parse_input(input);
+    let mut steps = 0;
+
+    loop {
+        let (new_map, moved) = sea_map.simulate_one_step();
+        steps += 1;
+
+        if moved == 0 {
+            return steps.to_string();
+        }
+
+        sea_map = new_map;
+    }
+}
+
+pub fn solve_bitboard() {
+    println!("{}", p1(ACTUAL_INPUT));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashmap;
+    use advent_of_code_2021_rust::rng::Rng;
+
+    const SAMPLE_INPUT: &str = r"
+v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>
+";
+
+    #[test]
+    fn test_rotate_across_words() {
+        let width = 70;
+        let mut row = Row::new(width);
+        row.set(0);
+        row.set(63);
+        row.set(69);
+
+        let right = row.rotate_right(width);
+        assert_eq!(
+            (0..width).filter(|x| right.get(*x)).collect::<Vec<_>>(),
+            vec![0, 1, 64]
+        );
+
+        let left = row.rotate_left(width);
+        assert_eq!(
+            (0..width).filter(|x| left.get(*x)).collect::<Vec<_>>(),
+            vec![62, 68, 69]
+        );
+    }
+
+    #[test]
+    fn test_simulate_one_step() {
+        let (sea_map, moved) = SeaMap::parse_input("...>>>>>...").simulate_one_step();
+        assert_eq!(moved, 1);
+        assert_eq!(sea_map, SeaMap::parse_input("...>>>>.>.."));
+
+        let (sea_map, moved) = SeaMap::parse_input("..>\n...\n..v").simulate_one_step();
+        assert_eq!(moved, 2);
+        assert_eq!(sea_map, SeaMap::parse_input(">.v\n...\n..."));
+    }
+
+    // the cell as it's drawn in the input
+    fn get(sea_map: &SeaMap, (x, y): (usize, usize)) -> char {
+        if sea_map.right[y].get(x) {
+            '>'
+        } else if sea_map.down[y].get(x) {
+            'v'
+        } else {
+            '.'
+        }
+    }
+
+    #[test]
+    fn test_matches_hashmap() {
+        // wider than a word, so the wraparound crosses word boundaries
+        let mut rng = Rng::new(0x2021_0025);
+        let input = (0..11)
+            .map(|_| {
+                (0..70)
+                    .map(|_| match rng.below(4) {
+                        0 => '>',
+                        1 => 'v',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // a random map may never settle, as a herd can keep going around
+        // the torus, so only the first steps are compared
+        [SAMPLE_INPUT, &input].iter().for_each(|input| {
+            let mut bitboard = SeaMap::parse_input(input);
+            let mut hashmap = hashmap::SeaMap::parse_input(input);
+            let height = bitboard.right.len();
+
+            (1..=100).try_for_each(|step| {
+                let (new_bitboard, bitboard_moved) = bitboard.simulate_one_step();
+                let (new_hashmap, hashmap_moved) = hashmap.simulate_one_step();
+                assert_eq!(bitboard_moved as i32, hashmap_moved, "step {}", step);
+
+                (0..height).for_each(|y| {
+                    (0..bitboard.width).for_each(|x| {
+                        assert_eq!(
+                            get(&new_bitboard, (x, y)),
+                            new_hashmap.get(&(x as i32, y as i32)),
+                            "step {} at ({}, {})",
+                            step,
+                            x,
+                            y
+                        );
+                    });
+                });
+
+                bitboard = new_bitboard;
+                hashmap = new_hashmap;
+                if bitboard_moved == 0 {
+                    None
+                } else {
+                    Some(())
+                }
+            });
+        });
+
+        assert_eq!(p1(SAMPLE_INPUT), hashmap::p1(SAMPLE_INPUT));
+    }
+
+    #[test]
+    fn test_p1_sample() {
+        assert_eq!(p1(SAMPLE_INPUT), "58");
+    }
+
+    #[test]
+    fn test_p1_actual() {
+        assert_eq!(p1(ACTUAL_INPUT), "308");
+    }
+}
@@ -0,0 +1,142 @@
+use std::io;
+
+use advent_of_code_2021_rust::{
+    automaton::{parse_rows, Automaton, Grid, Neighbourhood},
+    replay::{self, ReplayOptions},
+};
+
+const ACTUAL_INPUT: &str = include_str!("input.txt");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Empty,
+    Right,
+    Down,
+}
+
+// a herd moves into the cell in front of it, if the cell is empty.
+// `behind` and `ahead` are the neighbours along the herd's direction
+fn move_herd(cell: Cell, herd: Cell, behind: Cell, ahead: Cell) -> Cell {
+    if cell == Cell::Empty && behind == herd {
+        herd
+    } else if cell == herd && ahead == Cell::Empty {
+        Cell::Empty
+    } else {
+        cell
+    }
+}
+
+pub fn parse_grid(input: &str) -> Grid<Cell> {
+    Grid::toroidal(parse_rows(input, |c| match c {
+        '>' => Cell::Right,
+        'v' => Cell::Down,
+        '.' => Cell::Empty,
+        _ => panic!("Unknown character {}", c),
+    }))
+}
+
+fn create_automaton(input: &str) -> Automaton<'static, Cell> {
+    Automaton::new(parse_grid(input))
+        .with_phase(Neighbourhood::new(vec![(-1, 0), (1, 0)]), |cell, n| {
+            move_herd(cell, Cell::Right, n[0], n[1])
+        })
+        .with_phase(Neighbourhood::new(vec![(0, -1), (0, 1)]), |cell, n| {
+            move_herd(cell, Cell::Down, n[0], n[1])
+        })
+}
+
+fn p1(input: &str) -> String {
+    create_automaton(input)
+        .run_until_fixed_point(usize::MAX)
+        .unwrap()
+        .to_string()
+}
+
+fn p2(input: &str) -> String {
+    let _input = input.trim();
+    "".to_string()
+}
+
+pub fn replay_engine(options: &ReplayOptions) {
+    replay::replay(
+        &mut create_automaton(ACTUAL_INPUT),
+        options,
+        |cell| match cell {
+            Cell::Empty => '.',
+            Cell::Right => '>',
+            Cell::Down => 'v',
+        },
+        |cell| match cell {
+            Cell::Empty => [0, 0, 64],
+            Cell::Right => [255, 176, 0],
+            Cell::Down => [0, 200, 120],
+        },
+        |_| false,
+        &mut io::stdout(),
+    )
+    .unwrap();
+}
+
+pub fn solve_engine() {
+    println!("{}", p1(ACTUAL_INPUT));
+    println!("{}", p2(ACTUAL_INPUT));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_INPUT: &str = r"
+v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>
+";
+
+    #[test]
+    fn test_wraparound() {
+        let mut automaton = create_automaton("...>>>>>...");
+        automaton.step();
+        automaton.step();
+
+        assert_eq!(
+            automaton.grid().rows().next().unwrap(),
+            &parse_rows("...>>>.>.>.", |c| if c == '>' {
+                Cell::Right
+            } else {
+                Cell::Empty
+            })[0][..]
+        );
+
+        let mut automaton = create_automaton("..>\n...\n..v");
+        automaton.step();
+        assert_eq!(automaton.grid().get(0, 0), Some(Cell::Right));
+        assert_eq!(automaton.grid().get(2, 0), Some(Cell::Down));
+    }
+
+    #[test]
+    fn test_p1_sample() {
+        assert_eq!(p1(SAMPLE_INPUT), "58");
+    }
+
+    #[test]
+    fn test_p1_actual() {
+        assert_eq!(p1(ACTUAL_INPUT), "308");
+    }
+
+    #[test]
+    fn test_p2_sample() {
+        assert_eq!(p2(SAMPLE_INPUT), "");
+    }
+
+    #[test]
+    #[ignore = "not yet implemented"]
+    fn test_p2_actual() {
+        assert_eq!(p2(ACTUAL_INPUT), "");
+    }
+}
@@ -0,0 +1,107 @@
+// a table of integer columns, e.g. a series over time, that can be written
+// out as CSV or JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<i64>>,
+}
+
+impl Table {
+    pub fn new(columns: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<i64>) {
+        if row.len() != self.columns.len() {
+            panic!(
+                "Expected {} values, found {}",
+                self.columns.len(),
+                row.len()
+            );
+        }
+        self.rows.push(row);
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn column(&self, name: &str) -> Vec<i64> {
+        let index = self
+            .columns
+            .iter()
+            .position(|column| column == name)
+            .unwrap_or_else(|| panic!("Unknown column {}", name));
+        self.rows.iter().map(|row| row[index]).collect()
+    }
+
+    pub fn to_csv(&self) -> String {
+        std::iter::once(self.columns.join(","))
+            .chain(
+                self.rows
+                    .iter()
+                    .map(|row| row.iter().map(i64::to_string).collect::<Vec<_>>().join(",")),
+            )
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    // an array with one object per row; the column names are plain
+    // identifiers, so they aren't escaped
+    pub fn to_json(&self) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let fields = self
+                    .columns
+                    .iter()
+                    .zip(row.iter())
+                    .map(|(column, value)| format!("\"{}\":{}", column, value))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", fields.join(","))
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]\n", rows.join(",\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_table() -> Table {
+        let mut table = Table::new(&["index", "depth"]);
+        table.push(vec![0, 199]);
+        table.push(vec![1, -200]);
+        table
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(create_table().to_csv(), "index,depth\n0,199\n1,-200\n");
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            create_table().to_json(),
+            "[{\"index\":0,\"depth\":199},\n{\"index\":1,\"depth\":-200}]\n"
+        );
+        assert_eq!(Table::new(&["index"]).to_json(), "[]\n");
+    }
+
+    #[test]
+    fn test_column() {
+        assert_eq!(create_table().column("depth"), vec![199, -200]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_push_wrong_size() {
+        Table::new(&["index", "depth"]).push(vec![0]);
+    }
+}
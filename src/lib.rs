@@ -0,0 +1,8 @@
+pub mod automaton;
+pub mod bigint;
+pub mod export;
+pub mod ocr;
+pub mod ppm;
+pub mod replay;
+pub mod rng;
+pub mod svg;
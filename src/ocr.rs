@@ -0,0 +1,157 @@
+use std::fmt;
+
+// the 4x6 letters drawn by the puzzles, one glyph row per string
+const GLYPHS: [(char, [&str; HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const WIDTH: usize = 4;
+const HEIGHT: usize = 6;
+// glyphs are separated by a blank column
+const STRIDE: usize = WIDTH + 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    // the picture isn't a single row of letters
+    Height(usize),
+    // something is lit between two letters
+    Spacing { position: usize },
+    UnknownGlyph { position: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Height(height) => {
+                write!(f, "expected {} rows of pixels, found {}", HEIGHT, height)
+            }
+            OcrError::Spacing { position } => {
+                write!(f, "no gap after the letter at position {}", position)
+            }
+            OcrError::UnknownGlyph { position, glyph } => {
+                write!(f, "unknown glyph at position {}:\n{}", position, glyph)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+// lit pixels, row by row; short rows are padded with unlit pixels
+pub fn read_pixels(rows: &[Vec<bool>]) -> Result<String, OcrError> {
+    if rows.len() != HEIGHT {
+        return Err(OcrError::Height(rows.len()));
+    }
+
+    let is_lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let width = rows.iter().map(Vec::len).max().unwrap();
+
+    (0..width.div_ceil(STRIDE))
+        .map(|position| {
+            let left = position * STRIDE;
+            if (0..HEIGHT).any(|y| is_lit(left + WIDTH, y)) {
+                return Err(OcrError::Spacing { position });
+            }
+
+            let glyph = (0..HEIGHT)
+                .map(|y| {
+                    (left..left + WIDTH)
+                        .map(|x| if is_lit(x, y) { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+
+            GLYPHS
+                .iter()
+                .find(|(_, pattern)| pattern.iter().eq(glyph.iter()))
+                .map(|(letter, _)| *letter)
+                .ok_or_else(|| OcrError::UnknownGlyph {
+                    position,
+                    glyph: glyph.join("\n"),
+                })
+        })
+        .collect()
+}
+
+// a picture with '#' for lit pixels, where empty lines around it are ignored
+pub fn read_picture(picture: &str) -> Result<String, OcrError> {
+    read_pixels(
+        &picture
+            .trim_matches('\n')
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_picture() {
+        let picture = GLYPHS
+            .iter()
+            .map(|(_, pattern)| *pattern)
+            .fold(vec![String::new(); HEIGHT], |rows, pattern| {
+                rows.into_iter()
+                    .zip(pattern)
+                    .map(|(row, glyph_row)| row + glyph_row + ".")
+                    .collect()
+            })
+            .join("\n");
+
+        assert_eq!(read_picture(&picture).unwrap(), "ABCEFGHIJKLOPRSUZ");
+    }
+
+    #[test]
+    fn test_read_picture_trimmed() {
+        // the last letter ends in an unlit column, and nothing is drawn past it
+        assert_eq!(
+            read_picture(
+                r"
+#..#.###
+#..#.#..#
+####.#..#
+#..#.###
+#..#.#
+#..#.#
+"
+            )
+            .unwrap(),
+            "HP"
+        );
+    }
+
+    #[test]
+    fn test_read_picture_errors() {
+        assert_eq!(read_picture("####\n#..#"), Err(OcrError::Height(2)));
+        assert_eq!(
+            read_picture("#####\n#...#\n#...#\n#...#\n#...#\n#####"),
+            Err(OcrError::Spacing { position: 0 })
+        );
+        assert_eq!(
+            read_picture("####\n####\n####\n####\n####\n####"),
+            Err(OcrError::UnknownGlyph {
+                position: 0,
+                glyph: "####\n####\n####\n####\n####\n####".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,68 @@
+// binary PPM (P6), with the pixels given row by row
+pub fn encode(width: usize, height: usize, pixels: impl IntoIterator<Item = [u8; 3]>) -> Vec<u8> {
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    let header_len = ppm.len();
+
+    pixels.into_iter().for_each(|pixel| ppm.extend(pixel));
+
+    if ppm.len() - header_len != width * height * 3 {
+        panic!(
+            "Expected {} pixels, found {}",
+            width * height,
+            (ppm.len() - header_len) / 3
+        );
+    }
+
+    ppm
+}
+
+// every pixel becomes a `scale` x `scale` block
+pub fn encode_scaled(width: usize, height: usize, pixels: &[[u8; 3]], scale: usize) -> Vec<u8> {
+    encode(
+        width * scale,
+        height * scale,
+        (0..(height * scale)).flat_map(|y| {
+            (0..(width * scale)).map(move |x| pixels[(y / scale) * width + x / scale])
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            encode(2, 1, [[1, 2, 3], [4, 5, 6]]),
+            b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06".to_vec()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_encode_wrong_size() {
+        encode(2, 2, [[0, 0, 0]]);
+    }
+
+    #[test]
+    fn test_encode_scaled() {
+        assert_eq!(
+            encode_scaled(2, 1, &[[1, 1, 1], [2, 2, 2]], 2),
+            encode(
+                4,
+                2,
+                [
+                    [1, 1, 1],
+                    [1, 1, 1],
+                    [2, 2, 2],
+                    [2, 2, 2],
+                    [1, 1, 1],
+                    [1, 1, 1],
+                    [2, 2, 2],
+                    [2, 2, 2]
+                ]
+            )
+        );
+    }
+}
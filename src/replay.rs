@@ -0,0 +1,247 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    automaton::{Automaton, Grid},
+    ppm,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Display {
+    // redraws the frame in place
+    Ansi,
+    // prints every frame below the previous one
+    Plain,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayOptions {
+    pub display: Display,
+    pub delay: Duration,
+    // first and last generation to draw (the simulation stops after `to`)
+    pub from: usize,
+    pub to: Option<usize>,
+    pub ppm_directory: Option<PathBuf>,
+    // size of a cell in the PPM frames, in pixels
+    pub scale: usize,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            display: Display::Ansi,
+            delay: Duration::from_millis(100),
+            from: 0,
+            to: None,
+            ppm_directory: None,
+            scale: 4,
+        }
+    }
+}
+
+impl ReplayOptions {
+    // [--plain] [--delay <ms>] [--from <generation>] [--to <generation>]
+    // [--ppm <directory>] [--scale <pixels>]
+    pub fn parse_args(args: &[String]) -> Self {
+        let mut options = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("Missing value for {}", arg))
+            };
+
+            match arg.as_str() {
+                "--plain" => options.display = Display::Plain,
+                "--delay" => options.delay = Duration::from_millis(value().parse().unwrap()),
+                "--from" => options.from = value().parse().unwrap(),
+                "--to" => options.to = Some(value().parse().unwrap()),
+                "--ppm" => options.ppm_directory = Some(PathBuf::from(value())),
+                "--scale" => options.scale = value().parse().unwrap(),
+                _ => panic!("Unknown replay option {}", arg),
+            }
+        }
+
+        options
+    }
+}
+
+pub fn render_text<T: Copy + PartialEq>(grid: &Grid<T>, symbol: impl Fn(T) -> char) -> String {
+    grid.rows()
+        .map(|row| row.iter().map(|cell| symbol(*cell)).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn render_ppm<T: Copy + PartialEq>(
+    grid: &Grid<T>,
+    colour: impl Fn(T) -> [u8; 3],
+    scale: usize,
+) -> Vec<u8> {
+    let pixels = grid
+        .rows()
+        .flatten()
+        .map(|cell| colour(*cell))
+        .collect::<Vec<_>>();
+    ppm::encode_scaled(grid.width(), grid.height(), &pixels, scale)
+}
+
+// draws every generation, until `stop_when` holds, the automaton stops
+// changing, or the last generation in the options is reached
+pub fn replay<T: Copy + PartialEq>(
+    automaton: &mut Automaton<T>,
+    options: &ReplayOptions,
+    symbol: impl Fn(T) -> char,
+    colour: impl Fn(T) -> [u8; 3],
+    stop_when: impl Fn(&Grid<T>) -> bool,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    if let Some(directory) = &options.ppm_directory {
+        fs::create_dir_all(directory)?;
+    }
+
+    loop {
+        let generation = automaton.generation();
+
+        if generation >= options.from {
+            if options.display == Display::Ansi {
+                // clear the screen, and move the cursor to the top left
+                write!(out, "\x1b[2J\x1b[H")?;
+            }
+            writeln!(out, "Generation {}", generation)?;
+            writeln!(out, "{}", render_text(automaton.grid(), &symbol))?;
+            if options.display == Display::Plain {
+                writeln!(out)?;
+            }
+            out.flush()?;
+
+            if let Some(directory) = &options.ppm_directory {
+                fs::write(
+                    directory.join(format!("generation_{:04}.ppm", generation)),
+                    render_ppm(automaton.grid(), &colour, options.scale),
+                )?;
+            }
+
+            if !options.delay.is_zero() {
+                thread::sleep(options.delay);
+            }
+        }
+
+        if options.to.is_some_and(|to| generation >= to) || stop_when(automaton.grid()) {
+            return Ok(());
+        }
+
+        if !automaton.step() {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automaton::{parse_rows, Neighbourhood};
+
+    fn args(args: &str) -> Vec<String> {
+        args.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn create_automaton() -> Automaton<'static, bool> {
+        // a lit cell moves one step to the right, and falls off the edge
+        Automaton::new(Grid::bounded(parse_rows("#..", |c| c == '#')))
+            .with_phase(Neighbourhood::new(vec![(-1, 0)]), |_, left| {
+                left.first().copied().unwrap_or(false)
+            })
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(ReplayOptions::parse_args(&[]), ReplayOptions::default());
+        assert_eq!(
+            ReplayOptions::parse_args(&args(
+                "--plain --delay 0 --from 2 --to 5 --ppm frames --scale 1"
+            )),
+            ReplayOptions {
+                display: Display::Plain,
+                delay: Duration::ZERO,
+                from: 2,
+                to: Some(5),
+                ppm_directory: Some(PathBuf::from("frames")),
+                scale: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_replay_plain() {
+        let mut out = vec![];
+        let options = ReplayOptions::parse_args(&args("--plain --delay 0 --from 1"));
+
+        replay(
+            &mut create_automaton(),
+            &options,
+            |lit| if lit { '#' } else { '.' },
+            |_| [0, 0, 0],
+            |_| false,
+            &mut out,
+        )
+        .unwrap();
+
+        // stops once nothing changes anymore
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Generation 1\n.#.\n\nGeneration 2\n..#\n\nGeneration 3\n...\n\n"
+        );
+    }
+
+    #[test]
+    fn test_replay_stop() {
+        let mut out = vec![];
+        let options = ReplayOptions::parse_args(&args("--delay 0 --to 1"));
+
+        replay(
+            &mut create_automaton(),
+            &options,
+            |lit| if lit { '#' } else { '.' },
+            |_| [0, 0, 0],
+            |_| false,
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[2J\x1b[HGeneration 0\n#..\n\x1b[2J\x1b[HGeneration 1\n.#.\n"
+        );
+
+        let mut out = vec![];
+        replay(
+            &mut create_automaton(),
+            &ReplayOptions::parse_args(&args("--plain --delay 0")),
+            |lit| if lit { '#' } else { '.' },
+            |_| [0, 0, 0],
+            |grid| grid.get(1, 0) == Some(true),
+            &mut out,
+        )
+        .unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with("Generation 1\n.#.\n\n"));
+    }
+
+    #[test]
+    fn test_render_ppm() {
+        let grid = Grid::bounded(parse_rows("#.", |c| c == '#'));
+
+        assert_eq!(
+            render_ppm(&grid, |lit| if lit { [255, 0, 0] } else { [0, 0, 0] }, 1),
+            b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\x00".to_vec()
+        );
+    }
+}
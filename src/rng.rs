@@ -0,0 +1,45 @@
+// xorshift, so random inputs (stress inputs, property tests) are
+// reproducible without pulling in an extra crate
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // zero never changes under xorshift
+        Self(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // in 0..max
+    pub fn below(&mut self, max: u64) -> u64 {
+        if max == 0 {
+            panic!("Expected a non-empty range");
+        }
+        self.next_u64() % max
+    }
+
+    pub fn next_in_range(&mut self, min: i64, max_inclusive: i64) -> i64 {
+        min + self.below((max_inclusive - min + 1) as u64) as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_in_range() {
+        let mut rng = Rng::new(0);
+        (0..1000).for_each(|_| assert!((-3..=3).contains(&rng.next_in_range(-3, 3))));
+
+        // same seed, same numbers
+        let mut a = Rng::new(0x2021);
+        let mut b = Rng::new(0x2021);
+        (0..10).for_each(|_| assert_eq!(a.next_u64(), b.next_u64()));
+    }
+}
@@ -0,0 +1,84 @@
+const COLOURS: [&str; 4] = ["#1f77b4", "#d62728", "#2ca02c", "#ff7f0e"];
+const MARGIN: f64 = 20.0;
+
+// a line plot of every series against its index, all sharing the same axes;
+// larger values are drawn lower, which suits depths
+pub fn line_plot(series: &[(&str, Vec<i64>)], width: usize, height: usize) -> String {
+    let values = series.iter().flat_map(|(_, values)| values.iter().copied());
+    let min = values.clone().min().unwrap_or(0);
+    let max = values.max().unwrap_or(0);
+    let longest = series
+        .iter()
+        .map(|(_, values)| values.len())
+        .max()
+        .unwrap_or(0);
+
+    let plot_width = width as f64 - 2.0 * MARGIN;
+    let plot_height = height as f64 - 2.0 * MARGIN;
+    // a single point, or a flat series, still needs a non-zero range
+    let x_scale = plot_width / (longest.max(2) - 1) as f64;
+    let y_scale = plot_height / (max - min).max(1) as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+        width, height
+    );
+    svg += &format!(
+        "<rect x=\"{0}\" y=\"{0}\" width=\"{1}\" height=\"{2}\" fill=\"none\" stroke=\"#ccc\"/>\n",
+        MARGIN, plot_width, plot_height
+    );
+
+    series.iter().enumerate().for_each(|(i, (name, values))| {
+        let colour = COLOURS[i % COLOURS.len()];
+        let points = values
+            .iter()
+            .enumerate()
+            .map(|(x, y)| {
+                format!(
+                    "{:.1},{:.1}",
+                    MARGIN + x as f64 * x_scale,
+                    MARGIN + (y - min) as f64 * y_scale
+                )
+            })
+            .collect::<Vec<_>>();
+
+        svg += &format!(
+            "<polyline fill=\"none\" stroke=\"{}\" points=\"{}\"/>\n",
+            colour,
+            points.join(" ")
+        );
+        svg += &format!(
+            "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-size=\"12\">{}</text>\n",
+            MARGIN + (i * 100) as f64,
+            MARGIN - 6.0,
+            colour,
+            name
+        );
+    });
+
+    svg + "</svg>\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_plot() {
+        let svg = line_plot(&[("depth", vec![0, 10, 5])], 140, 60);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("points=\"20.0,20.0 70.0,40.0 120.0,30.0\""));
+        assert!(svg.contains(">depth</text>"));
+    }
+
+    #[test]
+    fn test_line_plot_flat() {
+        let svg = line_plot(&[("a", vec![3]), ("b", vec![3, 3])], 140, 60);
+
+        assert!(svg.contains("points=\"20.0,20.0\""));
+        assert!(svg.contains("points=\"20.0,20.0 120.0,20.0\""));
+        assert!(svg.contains("stroke=\"#d62728\""));
+    }
+}